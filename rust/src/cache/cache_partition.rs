@@ -1,14 +1,33 @@
+use crate::error::TectonicError;
+use crate::vector::stored_vector::{StorageMode, StoredVector};
+use crate::vector::vector_data::EntryStats;
 use crate::vector::vector_entry::VectorEntry;
 use crate::cache::cache_shard::CacheShard;
-use crate::utility::hashing_util::generate_vector_id;
-use crate::utility::vector_utils::{generate_vector_unique_id, scalar_quantize};
+use crate::cache::centroid_strategy::CentroidStrategy;
+use crate::utility::vector_utils::{binary_quantize, generate_vector_key, generate_vector_unique_id, hamming_distance};
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::filters::membership_filter::MembershipFilterDyn;
+use crate::metadata::cache_metrics::{PartitionMetrics, ShardMetrics};
+use crate::search::top_k_heap::TopKHeap;
+use crate::utility::hashing_util::{hash_u64, jump_consistent_hash};
+use crate::utility::kmeans::{squared_euclidean, KMeansConfig};
+use crate::utility::product_quantizer::ProductQuantizer;
+use crate::utility::scalar_quantizer::{CalibrationScope, ScalarQuantizer};
+use crate::utility::random_util::XorShiftRng;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::thread;
+
+/// Encoder learned from a partition's vectors during rebuild.
+pub enum PartitionCodec<const D: usize> {
+    Product(Arc<ProductQuantizer<D>>),
+    Scalar(ScalarQuantizer<D>),
+}
 
 #[derive(Clone)]
 #[allow(dead_code)]
-pub struct CachePartition<const D: usize> {
+pub struct CachePartition<const D: usize, V = ()> {
     /// Unique identifier for the cache partition (Immutable).
     pub partition_id: u64,
 
@@ -24,18 +43,54 @@ pub struct CachePartition<const D: usize> {
     /// K-means centroids representing the partition's vector clusters (Mutable).
     pub centroid: Option<[f32; D]>,
 
-    /// ID map for quick lookup of vector entries (Mutable).
-    pub id_map: HashMap<u64, [u8; D]>,
+    /// Whether inserts routed here went to a farther partition because this one was full, until the next rebuild (Mutable).
+    pub overflowed: bool,
+
+    /// Number of mutations since the centroid was last fully recalculated (Mutable).
+    pub mutation_count: usize,
+
+    /// Number of mutations before the centroid is fully recalculated (Immutable).
+    pub centroid_update: usize,
+
+    /// Whether the centroid is refreshed periodically or as a running mean (Immutable).
+    pub centroid_strategy: CentroidStrategy,
+
+    /// ID map from vector key (hash of the exact f32 bits) to entry ID for quick lookup (Mutable).
+    pub id_map: HashMap<u64, u64>,
+
+    /// Probabilistic filter over the id_map keys, consulted before map lookups (Mutable).
+    pub membership_filter: Option<Box<dyn MembershipFilterDyn>>,
 
     /// Internal storage for vector entries (Mutable).
-    pub entries: Vec<VectorEntry<D>>,
+    pub entries: Vec<VectorEntry<D, V>>,
 
     /// Internal storage for cache shards (Mutable).
-    pub shards: Vec<CacheShard<D>>,
+    pub shards: Vec<CacheShard<D, V>>,
+
+    /// Number of worker threads scanning shards in parallel (Immutable).
+    pub query_workers: usize,
+
+    /// Minimum number of vectors each worker scans before shards are scanned in parallel (Immutable).
+    pub parallel_threshold: usize,
+
+    /// Representation used for newly stored vectors (Immutable).
+    pub storage_mode: StorageMode,
+
+    /// Whether quantized entries keep their f32 source for re-ranking (Immutable).
+    pub retain_originals: bool,
+
+    /// Whether entries carry packed sign codes for binary (Hamming) queries (Immutable).
+    pub binary_signatures: bool,
+
+    /// Product-quantization codec trained on the partition's vectors during rebuild (Mutable).
+    pub product_quantizer: Option<Arc<ProductQuantizer<D>>>,
+
+    /// Int8 or Int4 grid calibrated over the partition's vectors during rebuild, one range for all dimensions (Mutable).
+    pub scalar_quantizer: Option<ScalarQuantizer<D>>,
 }
 
 #[allow(dead_code)]
-impl<const D: usize> CachePartition<D> {
+impl<const D: usize, V: Sync> CachePartition<D, V> {
     pub fn new(partition_id: u64, max_entries: usize, shard_count: usize) -> Self {
         Self {
             partition_id,
@@ -43,81 +98,620 @@ impl<const D: usize> CachePartition<D> {
             max_entries,
             entry_count: 0,
             centroid: None,
+            overflowed: false,
+            mutation_count: 0,
+            centroid_update: usize::MAX,
+            centroid_strategy: CentroidStrategy::default(),
             id_map: HashMap::new(),
+            membership_filter: None,
             entries: Vec::with_capacity(max_entries),
             shards: Vec::with_capacity(shard_count),
+            query_workers: 1,
+            parallel_threshold: usize::MAX,
+            storage_mode: StorageMode::default(),
+            retain_originals: false,
+            binary_signatures: false,
+            product_quantizer: None,
+            scalar_quantizer: None,
         }
     }
 
-    pub fn query(&self, _vector: &[f32; D], _top_k: usize) -> Vec<(u64, f32)> {
-        // Placeholder for actual query logic.
-        Vec::new()
+    pub fn scan<'a>(
+        &'a self,
+        vector: &[f32; D],
+        metric: &dyn DistanceMetricDyn<D>,
+        threshold: f32,
+        budget: usize,
+        heap: &mut TopKHeap<&'a VectorEntry<D, V>>,
+    ) -> usize {
+        // Tabulate partial distances once per query when the partition holds product codes.
+        let table = self.product_quantizer
+            .as_ref()
+            .zip(metric.adc_decomposition())
+            .map(|(quantizer, decomposition)| quantizer.lookup_table(vector, decomposition));
+        let table = table.as_ref();
+
+        self.scan_with(budget, heap, &|entry: &VectorEntry<D, V>| {
+            let distance = entry.vector.table_distance(vector, metric, table);
+            (distance <= threshold).then_some(distance)
+        })
     }
 
-    pub fn insert(&mut self, entry: &[f32; D], overwrite: bool) -> Result<bool, bool> {
-        // Placeholder for actual insert logic.
-        assert!(self.is_full(), "Cannot insert into a full partition");
+    /// Rank entries by Hamming distance between sign codes, the cheap first pass of a binary query.
+    pub fn scan_binary<'a>(&'a self, signature: &[u64], budget: usize, heap: &mut TopKHeap<&'a VectorEntry<D, V>>) -> usize {
+        self.scan_with(budget, heap, &|entry: &VectorEntry<D, V>| {
+            entry.signature.as_ref().map(|stored| hamming_distance(stored, signature) as f32)
+        })
+    }
 
-        let quantized_vector = scalar_quantize(entry, 256);
-        let map_id = generate_vector_id(&quantized_vector);
+    fn scan_with<'a, F>(&'a self, budget: usize, heap: &mut TopKHeap<&'a VectorEntry<D, V>>, score: &F) -> usize
+    where
+        F: Fn(&VectorEntry<D, V>) -> Option<f32> + Sync,
+    {
+        // Split the budget exactly as a serial walk over the shards would consume it.
+        let mut remaining = budget;
+        let allotments: Vec<usize> = self.shards
+            .iter()
+            .map(|shard| {
+                let allotment = shard.entries.len().min(remaining);
+                remaining -= allotment;
+                allotment
+            })
+            .collect();
+        let scanned = budget - remaining;
 
-        if self.id_map.contains_key(&map_id) {
-            if !overwrite {
-                let existing_vector = self.id_map.get(&map_id).unwrap();
-                if existing_vector == &quantized_vector {
-                    return Err(false); // Duplicate entry, insertion failed.
+        // Base Case -> Small scans are cheaper than spawning workers.
+        let workers = self.scan_workers(scanned);
+        if workers <= 1 {
+            for (shard, allotment) in self.shards.iter().zip(allotments) {
+                if allotment > 0 {
+                    shard.scan(allotment, heap, score);
                 }
-            self.id_map.remove(&map_id);
-            self.entry_count -= 1;
             }
-        };
+            return scanned;
+        }
+
+        // Fan out contiguous shard groups, each worker filling its own top-k heap.
+        let capacity = heap.capacity();
+        let group_size = self.shards.len().div_ceil(workers);
+        thread::scope(|scope| {
+            let handles: Vec<_> = self.shards
+                .chunks(group_size)
+                .zip(allotments.chunks(group_size))
+                .map(|(shards, allotments)| {
+                    scope.spawn(move || {
+                        let mut local = TopKHeap::new(capacity);
+                        for (shard, allotment) in shards.iter().zip(allotments) {
+                            shard.scan(*allotment, &mut local, score);
+                        }
+                        local
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                heap.merge(handle.join().expect("shard scan worker panicked"));
+            }
+        });
+
+        scanned
+    }
+
+    /// Workers spawned for a scan of this many vectors, each scanning at least parallel_threshold of them.
+    pub fn scan_workers(&self, scanned: usize) -> usize {
+        self.query_workers
+            .min(self.shards.len())
+            .min(scanned / self.parallel_threshold.max(1))
+    }
+
+    pub fn insert(&mut self, entry: &[f32; D], value: V, overwrite: bool) -> Result<u64, TectonicError> {
+        let map_id = generate_vector_key(entry);
+
+        // Identical vector already stored -> reject or replace its payload in place.
+        // Vectors merely sharing the key are distinct and stored as new entries.
+        if let Some(existing_id) = self.get_exact(entry, map_id).map(|existing| existing.entry_id) {
+            if !overwrite {
+                return Err(TectonicError::DuplicateEntry { key: map_id }); // Duplicate entry, insertion failed.
+            }
+            let (stored, signature) = (self.encode(entry), self.signature(entry));
+            if let Some(existing) = self.get_mut(existing_id) {
+                let old_vector = existing.vector.to_f32().into_owned();
+                existing.vector = stored;
+                existing.signature = signature;
+                existing.value = value;
+                existing.data.touch();
+                self.record_mutation(Some(entry), Some(&old_vector));
+                return Ok(existing_id);
+            }
+        }
+
+        if self.is_full() {
+            return Err(TectonicError::PartitionFull { partition_id: self.partition_id });
+        }
+        if self.shards.is_empty() {
+            return Err(TectonicError::EmptyPartition { partition_id: self.partition_id });
+        }
 
-        self.id_map.insert(map_id, quantized_vector);
         let atom_id = self.id_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) as u64;
         
         let vector_id = generate_vector_unique_id(self.partition_id, atom_id);
-        let shard_id = (vector_id % self.shards.len() as u64) as usize;
-        self.shards[shard_id].insert(entry, overwrite, vector_id);
+        let shard_id = self.placement_shard(vector_id)
+            .ok_or(TectonicError::PartitionFull { partition_id: self.partition_id })?;
+        let mut stored = VectorEntry::stored(vector_id, self.encode(entry), map_id, value);
+        stored.signature = self.signature(entry);
+        self.shards[shard_id].push_entry(stored)?;
+
+        self.index_key(map_id, vector_id);
+        self.entry_count += 1;
+        self.record_mutation(Some(entry), None);
+        Ok(vector_id)
+    }
+
+    /// Store an existing entry under its current ID, which may have been issued by another partition.
+    pub fn insert_entry(&mut self, mut entry: VectorEntry<D, V>) -> Result<u64, TectonicError> {
+        if self.is_full() {
+            return Err(TectonicError::PartitionFull { partition_id: self.partition_id });
+        }
+        if self.shards.is_empty() {
+            return Err(TectonicError::EmptyPartition { partition_id: self.partition_id });
+        }
+
+        let vector_id = entry.entry_id;
+        let shard_id = self.placement_shard(vector_id)
+            .ok_or(TectonicError::PartitionFull { partition_id: self.partition_id })?;
+
+        let map_id = entry.vector_key;
+        let vector = entry.vector.to_f32().into_owned();
+
+        // Codes from another codec or grid are re-encoded with this partition's.
+        if !self.is_native(&entry.vector) {
+            entry.vector = self.encode(&vector);
+        }
+        if self.binary_signatures && entry.signature.is_none() {
+            entry.signature = self.signature(&vector);
+        }
+        self.shards[shard_id].push_entry(entry)?;
+
+        self.index_key(map_id, vector_id);
         self.entry_count += 1;
-        Ok(true)
+        self.record_mutation(Some(&vector), None);
+        Ok(vector_id)
+    }
+
+    /// Stored form of a vector under the partition's storage mode.
+    fn encode(&self, vector: &[f32; D]) -> StoredVector<D> {
+        match (&self.product_quantizer, &self.scalar_quantizer) {
+            (Some(codec), _) => StoredVector::product(vector, codec, self.retain_originals),
+            (None, Some(quantizer)) if quantizer.covers(vector) => StoredVector::calibrated(vector, quantizer, self.retain_originals),
+            // Vectors outside the calibrated range keep their own range instead of saturating.
+            _ => StoredVector::encode(vector, self.storage_mode, self.retain_originals),
+        }
+    }
+
+    /// Whether a stored vector already uses the partition's trained codec or calibrated grid.
+    fn is_native(&self, stored: &StoredVector<D>) -> bool {
+        match (&self.product_quantizer, &self.scalar_quantizer) {
+            (Some(codec), _) => stored.is_encoded_by(codec),
+            (None, Some(quantizer)) => stored.is_calibrated_by(quantizer),
+            (None, None) => true,
+        }
+    }
+
+    /// Store sign codes for binary queries, computing them for entries that lack one.
+    pub fn enable_signatures(&mut self) {
+        self.binary_signatures = true;
+        for entry in self.shards.iter_mut().flat_map(|shard| shard.entries.iter_mut()) {
+            if entry.signature.is_none() {
+                entry.signature = Some(binary_quantize(&entry.vector.to_f32()));
+            }
+        }
+    }
+
+    /// Packed sign code of a vector, when binary queries are enabled.
+    fn signature(&self, vector: &[f32; D]) -> Option<Box<[u64]>> {
+        self.binary_signatures.then(|| binary_quantize(vector))
+    }
+
+    /// Train a product-quantization codec, or calibrate a scalar grid, on the given vectors when the
+    /// storage mode uses one. The partition is left untouched until the codec is installed.
+    pub fn train_codec(&self, vectors: &[&[f32; D]], kmeans_config: &KMeansConfig, rng: &mut XorShiftRng) -> Result<Option<PartitionCodec<D>>, TectonicError> {
+        // Base Case -> Nothing to learn from, keep the existing codec (if any).
+        if vectors.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(match self.storage_mode {
+            StorageMode::Product(config) => {
+                Some(PartitionCodec::Product(Arc::new(ProductQuantizer::train(vectors, config, kmeans_config, rng)?)))
+            }
+            StorageMode::Int8 | StorageMode::Int4 => {
+                let bits = self.storage_mode.scalar_bits().unwrap_or(8);
+                Some(PartitionCodec::Scalar(ScalarQuantizer::calibrate(vectors, bits, CalibrationScope::Shared)?))
+            }
+            StorageMode::Full | StorageMode::Half(_) => None,
+        })
+    }
+
+    /// Encode entries inserted from now on (including re-inserted ones) with a trained codec.
+    pub fn install_codec(&mut self, codec: PartitionCodec<D>) {
+        match codec {
+            PartitionCodec::Product(codec) => self.product_quantizer = Some(codec),
+            PartitionCodec::Scalar(quantizer) => self.scalar_quantizer = Some(quantizer),
+        }
+    }
+
+    /// Entry ID stored under a vector key, skipping the map when the filter rules it out.
+    pub fn lookup_key(&self, map_id: u64) -> Option<u64> {
+        if self.membership_filter.as_ref().is_some_and(|filter| !filter.contains(map_id)) {
+            return None;
+        }
+        self.id_map.get(&map_id).copied()
+    }
+
+    fn index_key(&mut self, map_id: u64, id: u64) {
+        // Base Case -> Key already recorded (in-place overwrite, or a distinct vector left to similarity scans).
+        if self.id_map.contains_key(&map_id) {
+            return;
+        }
+        self.id_map.insert(map_id, id);
+
+        // Filters that ran out of room, or outgrew their sizing with stale keys, are rebuilt.
+        if let Some(filter) = &mut self.membership_filter
+            && (!filter.insert(map_id) || filter.len() > self.max_entries.max(self.id_map.len()))
+        {
+            self.rebuild_filter();
+        }
+    }
+
+    fn unindex_key(&mut self, map_id: u64, id: u64) {
+        if self.id_map.get(&map_id) != Some(&id) {
+            return;
+        }
+        self.id_map.remove(&map_id);
+
+        // Non-deletable filters keep the key until their next rebuild.
+        if let Some(filter) = &mut self.membership_filter {
+            filter.remove(map_id);
+        }
+    }
+
+    /// Rebuild the membership filter from id_map, sized for the current capacity.
+    pub fn rebuild_filter(&mut self) {
+        let Some(filter) = &self.membership_filter else {
+            return;
+        };
+
+        let mut rebuilt = filter.resized(self.max_entries.max(self.id_map.len() * 2));
+        for map_id in self.id_map.keys() {
+            rebuilt.insert(*map_id);
+        }
+        self.membership_filter = Some(rebuilt);
+    }
+
+    /// Home shard of an entry ID under jump consistent hashing.
+    pub fn home_shard(&self, id: u64) -> usize {
+        jump_consistent_hash(hash_u64(id), self.shards.len())
+    }
+
+    /// Home shard if it has capacity, otherwise the next sibling shard with space.
+    fn placement_shard(&self, id: u64) -> Option<usize> {
+        let home = self.home_shard(id);
+        (0..self.shards.len())
+            .map(|offset| (home + offset) % self.shards.len())
+            .find(|idx| !self.shards[*idx].is_full())
     }
 
-    pub fn metrics(&self) -> String {
-        // Placeholder for metrics implementation.
-        "Partition metrics not implemented".to_string()
+    /// Check that the partition can be resized to the given number of shards.
+    pub fn validate_shard_count(&self, shard_count: usize) -> Result<(), TectonicError> {
+        if shard_count == 0 || shard_count > self.max_entries.max(1) {
+            return Err(TectonicError::InvalidConfig(format!(
+                "shard count must be within [1, {}], got {}",
+                self.max_entries.max(1),
+                shard_count
+            )));
+        }
+        Ok(())
+    }
+
+    /// Grow or shrink the number of shards, returning how many entries changed shard.
+    pub fn resize_shards(&mut self, shard_count: usize) -> Result<usize, TectonicError> {
+        self.validate_shard_count(shard_count)?;
+        let sizes = Self::calculate_shard_size(self.max_entries, shard_count)?;
+
+        // Entries held by removed shards must be rehoused.
+        let mut displaced: Vec<VectorEntry<D, V>> = Vec::new();
+        while self.shards.len() > shard_count {
+            if let Some(mut shard) = self.shards.pop() {
+                displaced.extend(shard.drain());
+            }
+        }
+        while self.shards.len() < shard_count {
+            self.shards.push(CacheShard::new(self.shards.len() as u64, 0));
+        }
+        for (shard, size) in self.shards.iter_mut().zip(sizes) {
+            shard.max_entries = size;
+        }
+
+        // Collect entries whose jump-hash home changed or that no longer fit.
+        for shard_idx in 0..self.shards.len() {
+            let mut kept = Vec::with_capacity(self.shards[shard_idx].entries.len());
+            for entry in self.shards[shard_idx].drain() {
+                let fits = kept.len() < self.shards[shard_idx].max_entries;
+                if fits && self.home_shard(entry.entry_id) == shard_idx {
+                    kept.push(entry);
+                } else {
+                    displaced.push(entry);
+                }
+            }
+            for entry in kept {
+                self.shards[shard_idx].push_entry(entry)?;
+            }
+        }
+
+        // Rehouse displaced entries at their new home, spilling to siblings when full.
+        let moved = displaced.len();
+        for entry in displaced {
+            let shard_idx = self.placement_shard(entry.entry_id)
+                .ok_or(TectonicError::PartitionFull { partition_id: self.partition_id })?;
+            self.shards[shard_idx].push_entry(entry)?;
+        }
+        Ok(moved)
+    }
+
+    /// Change the partition capacity, redistributing it across the existing shards.
+    pub fn set_capacity(&mut self, max_entries: usize) -> Result<(), TectonicError> {
+        if max_entries < self.entry_count {
+            return Err(TectonicError::InvalidConfig(format!(
+                "capacity {} is below the {} stored entries",
+                max_entries, self.entry_count
+            )));
+        }
+        self.max_entries = max_entries;
+        self.rebuild_filter();
+        self.resize_shards(self.shards.len()).map(|_| ())
+    }
+
+    /// Remove and return every stored entry, leaving the partition empty.
+    pub fn drain(&mut self) -> Vec<VectorEntry<D, V>> {
+        let entries: Vec<VectorEntry<D, V>> = self.shards.iter_mut().flat_map(|shard| shard.drain()).collect();
+        self.id_map.clear();
+        if let Some(filter) = &mut self.membership_filter {
+            filter.clear();
+        }
+        self.entry_count = 0;
+        self.centroid = None;
+        self.mutation_count = 0;
+        entries
+    }
+
+    pub fn get(&self, id: u64) -> Option<&VectorEntry<D, V>> {
+        // Base Case -> No shards defined.
+        if self.shards.is_empty() {
+            return None;
+        }
+
+        // Check the hashed home shard first, then fall back to spilled entries.
+        let home = self.home_shard(id);
+        self.shards[home].get(id).or_else(|| self.shards.iter().find_map(|shard| shard.get(id)))
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut VectorEntry<D, V>> {
+        let shard_idx = self.shards.iter().position(|shard| shard.get(id).is_some())?;
+        self.shards[shard_idx].get_mut(id)
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<VectorEntry<D, V>> {
+        // Base Case -> No shards defined.
+        if self.shards.is_empty() {
+            return None;
+        }
+
+        // Check the hashed home shard first, then fall back to spilled entries.
+        let home = self.home_shard(id);
+        let removed = self.shards[home].remove(id).or_else(|| {
+            self.shards
+                .iter_mut()
+                .enumerate()
+                .filter(|(idx, _)| *idx != home)
+                .find_map(|(_, shard)| shard.remove(id))
+        })?;
+
+        // Keep the duplicate lookup map and filter in sync with shard contents.
+        self.unindex_key(removed.vector_key, id);
+        self.entry_count -= 1;
+        self.record_mutation(None, Some(&removed.vector.to_f32()));
+        Some(removed)
+    }
+
+    pub fn update(&mut self, id: u64, vector: &[f32; D]) -> Result<(), TectonicError> {
+        let new_map_id = generate_vector_key(vector);
+
+        // Reject updates that would duplicate a different stored vector.
+        if self.get_exact(vector, new_map_id).is_some_and(|existing| existing.entry_id != id) {
+            return Err(TectonicError::DuplicateEntry { key: new_map_id });
+        }
+
+        let (stored, signature) = (self.encode(vector), self.signature(vector));
+        let entry = self.get_mut(id).ok_or(TectonicError::EntryNotFound { entry_id: id })?;
+        let old_vector = entry.vector.to_f32().into_owned();
+        let old_map_id = std::mem::replace(&mut entry.vector_key, new_map_id);
+        entry.vector = stored;
+        entry.signature = signature;
+
+        // Re-key the duplicate lookup map and filter to the new vector contents.
+        self.unindex_key(old_map_id, id);
+        self.index_key(new_map_id, id);
+        self.record_mutation(Some(vector), Some(&old_vector));
+        Ok(())
+    }
+
+    /// Mean squared Euclidean distance of stored vectors to the centroid.
+    pub fn variance(&self) -> Option<f32> {
+        let centroid = self.centroid.as_ref()?;
+        if self.entry_count == 0 {
+            return None;
+        }
+
+        let total: f32 = self.shards
+            .iter()
+            .flat_map(|shard| shard.entries.iter())
+            .map(|entry| squared_euclidean(&entry.vector.to_f32(), centroid))
+            .sum();
+        Some(total / self.entry_count as f32)
+    }
+
+    /// Ratio of stored entries to partition capacity.
+    pub fn fill_ratio(&self) -> f32 {
+        if self.max_entries == 0 {
+            return 1.0;
+        }
+        self.entry_count as f32 / self.max_entries as f32
+    }
+
+    /// Entry stored under a vector key, resolved through the membership filter.
+    pub fn get_by_key(&self, map_id: u64) -> Option<&VectorEntry<D, V>> {
+        self.lookup_key(map_id).and_then(|id| self.get(id))
+    }
+
+    /// Entry stored from exactly this vector, located through the key map.
+    pub fn get_exact(&self, vector: &[f32; D], map_id: u64) -> Option<&VectorEntry<D, V>> {
+        self.get_by_key(map_id).filter(|entry| entry.matches(vector, map_id))
+    }
+
+    /// Heap bytes held by stored vectors across all shards.
+    pub fn vector_bytes(&self) -> usize {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.entries.iter())
+            .map(|entry| entry.vector.size_bytes())
+            .sum()
+    }
+
+    pub fn entry_stats(&self, id: u64) -> Option<EntryStats> {
+        self.get(id).map(|entry| entry.data.stats())
     }
 
-    fn calculate_shard_size(max_entries: usize, shard_count: usize) -> Vec<usize> {
+    pub fn contains(&self, id: u64) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Partition and per-shard fill at the time of the call.
+    pub fn metrics(&self) -> PartitionMetrics {
+        PartitionMetrics {
+            partition_id: self.partition_id,
+            entries: self.entry_count,
+            capacity: self.max_entries,
+            shards: self.shards
+                .iter()
+                .map(|shard| ShardMetrics { shard_id: shard.shard_id, entries: shard.entry_count, capacity: shard.max_entries })
+                .collect(),
+        }
+    }
+
+    fn calculate_shard_size(max_entries: usize, shard_count: usize) -> Result<Vec<usize>, TectonicError> {
         // Base Case -> No shards defined.
-        assert!(shard_count > 0, "Shard count must be greater than 0");
+        if shard_count == 0 {
+            return Err(TectonicError::InvalidConfig("shard count must be greater than 0".to_string()));
+        }
 
         // Evenly distribute max_entries across shards.
         let base = max_entries / shard_count;
         let remainder = max_entries % shard_count;
 
         // Allocate reamainders to individual shards to ensure total matches max_entries.
-        let mut sizes = vec![base; shard_count as usize];
-        for i in 0..remainder as usize {
-            sizes[i] += 1;
+        let mut sizes = vec![base; shard_count];
+        for size in sizes.iter_mut().take(remainder) {
+            *size += 1;
         }
 
         // Return calculated shard sizes.
-        sizes
+        Ok(sizes)
     }
 
-    pub fn initiate_shards(&mut self, total_size: usize, shard_count: usize) {
+    pub fn initiate_shards(&mut self, total_size: usize, shard_count: usize) -> Result<(), TectonicError> {
         // Calcuate shard sizes based on total partition size and number of shards.
-        let sizes = Self::calculate_shard_size(total_size, shard_count);
+        let sizes = Self::calculate_shard_size(total_size, shard_count)?;
         
         // Initialize shards with calculated sizes and unique shard IDs.
         for (shard_id, size) in sizes.iter().enumerate() {
             self.shards.push(CacheShard::new(shard_id as u64, *size));
         }
+        Ok(())
+    }
+
+    pub fn update_centroid(&mut self) -> Result<(), TectonicError> {
+        if self.shards.is_empty() {
+            return Err(TectonicError::EmptyPartition { partition_id: self.partition_id });
+        }
+
+        self.recalculate_centroid();
+        Ok(())
+    }
+
+    pub fn is_centroid_stale(&self) -> bool {
+        self.mutation_count > 0
+    }
+
+    fn record_mutation(&mut self, added: Option<&[f32; D]>, removed: Option<&[f32; D]>) {
+        self.mutation_count += 1;
+
+        // Full recalculation once the mutation threshold is crossed.
+        if self.mutation_count >= self.centroid_update {
+            self.recalculate_centroid();
+            return;
+        }
+
+        // Seed an unset centroid so routing and probing can rank the partition.
+        if self.centroid.is_none() {
+            self.centroid = self.compute_centroid();
+            return;
+        }
+
+        if self.centroid_strategy == CentroidStrategy::Incremental {
+            self.apply_running_mean(added, removed);
+        }
     }
 
-    pub fn update_centroid(&mut self){
-        assert!(self.shards.is_empty(), "Cannot update centroid for an empty partition");
+    fn apply_running_mean(&mut self, added: Option<&[f32; D]>, removed: Option<&[f32; D]>) {
+        let count = self.entry_count as f32;
 
+        match (self.centroid.as_mut(), added, removed) {
+            // Base Case -> Partition emptied, centroid is undefined.
+            (_, _, _) if self.entry_count == 0 => self.centroid = None,
+
+            // Update -> shift the mean by the difference between new and old vectors.
+            (Some(centroid), Some(added), Some(removed)) => {
+                for i in 0..D {
+                    centroid[i] += (added[i] - removed[i]) / count;
+                }
+            }
+
+            // Insert -> c' = c + (x - c) / n.
+            (Some(centroid), Some(added), None) => {
+                for (value, x) in centroid.iter_mut().zip(added.iter()) {
+                    *value += (*x - *value) / count;
+                }
+            }
+
+            // Remove -> c' = (c * (n + 1) - x) / n.
+            (Some(centroid), None, Some(removed)) => {
+                for (value, x) in centroid.iter_mut().zip(removed.iter()) {
+                    *value = (*value * (count + 1.0) - *x) / count;
+                }
+            }
+
+            // No running mean yet -> seed it from the stored vectors.
+            _ => self.recalculate_centroid(),
+        }
+    }
+
+    fn recalculate_centroid(&mut self) {
+        self.mutation_count = 0;
+        self.centroid = self.compute_centroid();
+    }
+
+    /// Mean of the stored vectors, calculated without modifying the partition.
+    pub fn compute_centroid(&self) -> Option<[f32; D]> {
         let mut total_entries = 0;
         let mut mean = [0.0f32; D];
         
@@ -131,8 +725,13 @@ impl<const D: usize> CachePartition<D> {
             
         }
 
+        // Base Case -> No stored vectors, centroid is undefined.
+        if total_entries == 0 {
+            return None;
+        }
+
         mean.iter_mut().for_each(|x| *x /= total_entries as f32);
-        self.centroid = Some(mean);
+        Some(mean)
     }
 
     fn is_full(&self) -> bool {
@@ -1,8 +1,10 @@
+use crate::error::TectonicError;
 use crate::vector::vector_entry::VectorEntry;
+use crate::search::top_k_heap::TopKHeap;
 
 #[derive(Clone)]
 #[allow(dead_code)]
-pub struct CacheShard<const D: usize> {
+pub struct CacheShard<const D: usize, V = ()> {
     /// Unique identifier for the cache shard (Immutable).
     pub shard_id: u64,
 
@@ -13,11 +15,11 @@ pub struct CacheShard<const D: usize> {
     pub entry_count: usize,
 
     /// Internal storage for cache partitions (Mutable).
-    pub entries: Vec<VectorEntry<D>>,
+    pub entries: Vec<VectorEntry<D, V>>,
 }
 
 #[allow(dead_code)]
-impl <const D: usize> CacheShard<D> {
+impl <const D: usize, V> CacheShard<D, V> {
     pub fn new(shard_id: u64, max_entries: usize) -> Self {
         Self {
             shard_id,
@@ -27,24 +29,50 @@ impl <const D: usize> CacheShard<D> {
         }
     }
 
-    pub fn insert(&mut self, vector: &[f32; D], overwrite: bool, id: u64) -> bool {
-        if self.is_full() {
-            return false; // Shard is full, cannot insert.
+    /// Score entries until the candidate budget is exhausted, keeping those the scorer accepts.
+    pub fn scan<'a, F>(&'a self, budget: usize, heap: &mut TopKHeap<&'a VectorEntry<D, V>>, score: &F) -> usize
+    where
+        F: Fn(&VectorEntry<D, V>) -> Option<f32>,
+    {
+        let mut scanned = 0;
+        for entry in self.entries.iter().take(budget) {
+            scanned += 1;
+            if let Some(distance) = score(entry) {
+                heap.push(distance, entry);
+            }
         }
 
-        // Check for existing entry if overwrite is false.
-        if !overwrite {
-            for entry in &self.entries {
-                if entry.vector == *vector {
-                    return false; // Duplicate entry found, insertion aborted.
-                }
-            }
+        scanned
+    }
+
+    /// Store an entry, duplicates are rejected beforehand by the owning partition's key map.
+    pub fn push_entry(&mut self, entry: VectorEntry<D, V>) -> Result<(), TectonicError> {
+        if self.is_full() {
+            return Err(TectonicError::ShardFull { shard_id: self.shard_id });
         }
 
-        // Insert the new vector entry.
-        self.entries.push(VectorEntry::new(id, *vector));
+        self.entries.push(entry);
         self.entry_count += 1;
-        true
+        Ok(())
+    }
+
+    pub fn drain(&mut self) -> Vec<VectorEntry<D, V>> {
+        self.entry_count = 0;
+        std::mem::take(&mut self.entries)
+    }
+
+    pub fn get(&self, id: u64) -> Option<&VectorEntry<D, V>> {
+        self.entries.iter().find(|entry| entry.entry_id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut VectorEntry<D, V>> {
+        self.entries.iter_mut().find(|entry| entry.entry_id == id)
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<VectorEntry<D, V>> {
+        let position = self.entries.iter().position(|entry| entry.entry_id == id)?;
+        self.entry_count -= 1;
+        Some(self.entries.swap_remove(position))
     }
 
     pub fn get_shard_centroid(&self) -> Option<([f32; D], f32)> {
@@ -56,15 +84,15 @@ impl <const D: usize> CacheShard<D> {
         let mut mean = [0.0f32; D];
 
         for entry in &self.entries {
-            for index in 0..D {
-                mean[index] += entry.vector[index];
+            for (sum, value) in mean.iter_mut().zip(entry.vector.to_f32().iter()) {
+                *sum += *value;
             }
         };
 
         Some((mean, count))
     }
 
-    fn is_full(&self) -> bool {
+    pub fn is_full(&self) -> bool {
         self.entry_count >= self.max_entries
     }
     
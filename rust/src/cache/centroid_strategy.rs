@@ -0,0 +1,12 @@
+
+/// How partition centroids are kept up to date as vectors are mutated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CentroidStrategy {
+    /// Recalculate the centroid from all stored vectors every `centroid_update` mutations.
+    #[default]
+    Periodic,
+
+    /// Adjust the centroid as a running mean on every mutation, with a full
+    /// recalculation every `centroid_update` mutations to correct float drift.
+    Incremental,
+}
@@ -0,0 +1,575 @@
+use crate::cache::cache_partition::CachePartition;
+use crate::cache::centroid_strategy::CentroidStrategy;
+use crate::cache::partition_search::{PartitionSearch, PartitionState};
+use crate::cache::vector_cache::VectorCache;
+use crate::cache::vector_cache_builder::VectorCacheBuilder;
+use crate::error::TectonicError;
+use crate::eviction::eviction_policy::{EvictionCandidate, EvictionPolicyDyn};
+use crate::metadata::cache_metrics::CacheMetrics;
+use crate::metadata::metrics_recorder::MetricsRecorder;
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::search::probe_mode::ProbeMode;
+use crate::search::query_mode::QueryMode;
+use crate::search::search_result::SearchResult;
+use crate::vector::stored_vector::StorageMode;
+use crate::vector::vector_data::EntryStats;
+use crate::vector::vector_entry::VectorEntry;
+use crate::utility::vector_utils::{decode_vector_unique_id, generate_vector_key};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Instant;
+
+/* ==============================
+    * Concurrent Vector Cache
+    *
+    * Thread-safe counterpart of VectorCache that can be shared between request
+    * handlers (e.g. behind an Arc). Queries and inserts take &self:
+    * - Every partition sits behind its own RwLock, so queries only share-lock
+    *   the partitions they probe and inserts only exclusively lock their target.
+    * - Routing reads published centroid snapshots, and periodic centroid
+    *   recalculation runs under a shared lock, so it never blocks readers.
+    * - Inserts of identical vectors are serialised through lock
+    *   striping on the vector key, keeping duplicate detection exact.
+    * - Whenever several partition locks are held at once they are taken in
+    *   ascending partition index, so readers never deadlock behind a writer.
+    *
+    * Partition topology is fixed; dynamic partitioning and rebuilds require the
+    * single-threaded VectorCache.
+============================== */
+
+/// Number of lock stripes serialising inserts that share a vector key.
+const INSERT_LOCK_STRIPES: usize = 64;
+
+struct PartitionSlot<const D: usize, V> {
+    /// Identifier of the wrapped partition, used to resolve entry IDs (Immutable).
+    partition_id: u64,
+
+    /// Centroid published for routing, readable without locking the partition (Mutable).
+    centroid: RwLock<Option<[f32; D]>>,
+
+    /// Published entry count, readable without locking the partition (Mutable).
+    entry_count: AtomicUsize,
+
+    /// Published overflow flag, set once inserts routed here went to a farther partition (Mutable).
+    overflowed: AtomicBool,
+
+    /// Mutations since the published centroid was last recalculated (Mutable).
+    mutations: AtomicUsize,
+
+    /// Partition storage guarded by a reader-writer lock (Mutable).
+    partition: RwLock<CachePartition<D, V>>,
+}
+
+impl<const D: usize, V> PartitionSlot<D, V> {
+    fn read(&self) -> RwLockReadGuard<'_, CachePartition<D, V>> {
+        self.partition.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, CachePartition<D, V>> {
+        self.partition.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn centroid(&self) -> Option<[f32; D]> {
+        *self.centroid.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn publish_centroid(&self, centroid: Option<[f32; D]>) {
+        *self.centroid.write().unwrap_or_else(PoisonError::into_inner) = centroid;
+    }
+}
+
+#[allow(dead_code)]
+pub struct ConcurrentVectorCache<const D: usize, V = ()> {
+    /// Human-readable cache identifier (Debugging, Metrics, Logging).
+    cache_id: String,
+
+    /// Creation timestamp (Debugging, Metrics).
+    created_at: Instant,
+
+    /// Maximum number of high-dimensional vectors able to be stored in the cache.
+    max_entries: usize,
+
+    /// Number of mutations before a partition centroid is republished (Immutable).
+    centroid_update: usize,
+
+    /// Whether centroids are refreshed periodically or as a running mean (Immutable).
+    centroid_strategy: CentroidStrategy,
+
+    /// Vector distance / similarity metric utilised during queries (Immutable).
+    search_metric: Box<dyn DistanceMetricDyn<D>>,
+
+    /// Maximum number of vectors examined per query (Immutable).
+    search_candidates: usize,
+
+    /// Whether candidates are scored directly or prefiltered by Hamming distance (Immutable).
+    query_mode: QueryMode,
+
+    /// Representation shards use for stored vectors (Immutable).
+    storage_mode: StorageMode,
+
+    /// Multiple of top_k scored on quantized vectors before the f32 re-rank (Immutable).
+    rerank_oversampling: usize,
+
+    /// Number of partitions probed per query, ranked by centroid distance (Immutable).
+    probe_mode: ProbeMode,
+
+    /// Customisable eviction strategy implemented for vector replacement (Immutable).
+    eviction_strategy: Box<dyn EvictionPolicyDyn<D>>,
+
+    /// Flag to determine whether inserts are allowed to trigger immediate eviction (Immutable).
+    eager_eviction: bool,
+
+    /// Atomic activity counters and latency histogram (Mutable).
+    metrics: MetricsRecorder,
+
+    /// Stored entries plus in-flight insert reservations (Mutable).
+    entry_count: AtomicUsize,
+
+    /// Lock stripes keyed by vector key (Mutable).
+    insert_locks: Vec<Mutex<()>>,
+
+    /// Lock-protected partitions for vector storage and management (Mutable).
+    partitions: Vec<PartitionSlot<D, V>>,
+}
+
+#[allow(dead_code)]
+impl<const D: usize, V: Clone + Send + Sync> ConcurrentVectorCache<D, V> {
+    pub fn builder() -> VectorCacheBuilder<D, V> {
+        VectorCacheBuilder::new()
+    }
+
+    pub(crate) fn from_builder(builder: VectorCacheBuilder<D, V>) -> Result<Self, TectonicError> {
+        if !builder.thread_safe {
+            return Err(TectonicError::InvalidConfig("thread_safe must be enabled for a concurrent cache".to_string()));
+        }
+        if builder.dynamic_partitioning.is_some() {
+            return Err(TectonicError::InvalidConfig("dynamic partitioning is not supported by the concurrent cache".to_string()));
+        }
+
+        let partitions = VectorCache::initialize_partitions(&builder)?
+            .into_iter()
+            .map(|mut partition| {
+                // Periodic recalculation is published by the cache outside the write lock.
+                if builder.centroid_strategy == CentroidStrategy::Periodic {
+                    partition.centroid_update = usize::MAX;
+                }
+                PartitionSlot {
+                    partition_id: partition.partition_id,
+                    centroid: RwLock::new(None),
+                    entry_count: AtomicUsize::new(0),
+                    overflowed: AtomicBool::new(false),
+                    mutations: AtomicUsize::new(0),
+                    partition: RwLock::new(partition),
+                }
+            })
+            .collect();
+        let storage_mode = builder.storage_mode();
+        let search_metric = match builder.custom_metric {
+            Some(metric) => metric,
+            None => VectorCache::<D, V>::initialise_search_metric(builder.search_metric)?,
+        };
+        let eviction_strategy = match builder.custom_eviction {
+            Some(policy) => policy,
+            None => VectorCache::<D, V>::initialise_eviction_policy(builder.eviction_strategy)?,
+        };
+
+        Ok(Self {
+            partitions,
+            cache_id: builder.cache_id,
+            created_at: Instant::now(),
+            max_entries: builder.max_entries,
+            centroid_update: builder.centroid_update,
+            centroid_strategy: builder.centroid_strategy,
+            search_metric,
+            search_candidates: builder.search_candidates,
+            query_mode: builder.query_mode,
+            storage_mode,
+            rerank_oversampling: builder.rerank_oversampling,
+            probe_mode: builder.probe_mode,
+            eviction_strategy,
+            eager_eviction: builder.eager_eviction,
+            metrics: MetricsRecorder::new(builder.metrics_enabled),
+            entry_count: AtomicUsize::new(0),
+            insert_locks: (0..INSERT_LOCK_STRIPES).map(|_| Mutex::new(())).collect(),
+        })
+    }
+
+    pub fn query(&self, vector: &[f32], top_k: usize, threshold: f32) -> Result<Vec<SearchResult<D, V>>, TectonicError> {
+        self.query_with_probe(vector, top_k, threshold, self.probe_mode)
+    }
+
+    pub fn query_with_probe(&self, vector: &[f32], top_k: usize, threshold: f32, probe_mode: ProbeMode) -> Result<Vec<SearchResult<D, V>>, TectonicError> {
+        let vector = VectorCache::<D, V>::as_dimensioned(vector)?;
+        let started = self.metrics.start();
+
+        let results = self.search(vector, top_k, threshold, probe_mode);
+        self.metrics.record_query(started, !results.is_empty());
+        Ok(results)
+    }
+
+    fn search(&self, vector: &[f32; D], top_k: usize, threshold: f32, probe_mode: ProbeMode) -> Vec<SearchResult<D, V>> {
+        let searcher = self.searcher();
+
+        // Share-lock only the probed partitions (in index order), then scan closest centroids first.
+        let probes = searcher.probes(vector, &self.partition_states(), probe_mode);
+        let mut locked: Vec<usize> = probes.clone();
+        locked.sort_unstable();
+        let mut guards: Vec<_> = locked.iter().map(|idx| (*idx, self.partitions[*idx].read())).collect();
+        guards.sort_by_key(|(idx, _)| probes.iter().position(|probe| probe == idx));
+
+        // Clone matches out before the partition locks are released.
+        searcher
+            .search(guards.iter().map(|(_, partition)| &**partition), vector, top_k, threshold)
+            .into_iter()
+            .map(|(score, entry)| SearchResult::new(entry.clone(), score))
+            .collect()
+    }
+
+    /// Return a copy of the payload of the most similar entry within the distance threshold.
+    /// Exact matches are answered by the partition filters without a similarity scan.
+    pub fn get_similar(&self, vector: &[f32], threshold: f32) -> Result<Option<V>, TectonicError> {
+        let vector = VectorCache::<D, V>::as_dimensioned(vector)?;
+        let started = self.metrics.start();
+
+        let value = match self.find_exact(vector, threshold) {
+            Some(exact) => Some(exact.entry.value),
+            None => self.search(vector, 1, threshold, self.probe_mode)
+                .into_iter()
+                .next()
+                .map(|result| result.entry.value),
+        };
+
+        self.metrics.record_query(started, value.is_some());
+        Ok(value)
+    }
+
+    /// Return the entry stored from exactly this vector (bit-for-bit), probing one filter per partition.
+    pub fn get_exact(&self, vector: &[f32]) -> Result<Option<SearchResult<D, V>>, TectonicError> {
+        let vector = VectorCache::<D, V>::as_dimensioned(vector)?;
+        let started = self.metrics.start();
+
+        let result = self.find_exact(vector, f32::INFINITY);
+        self.metrics.record_query(started, result.is_some());
+        Ok(result)
+    }
+
+    fn find_exact(&self, vector: &[f32; D], threshold: f32) -> Option<SearchResult<D, V>> {
+        let started = Instant::now();
+        let map_id = generate_vector_key(vector);
+
+        // Partitions are share-locked one at a time, cloning the match out under the lock.
+        let searcher = self.searcher();
+        self.partitions.iter().find_map(|slot| {
+            searcher
+                .exact_match(&slot.read(), vector, map_id, threshold, started)
+                .map(|(score, entry)| SearchResult::new(entry.clone(), score))
+        })
+    }
+
+    fn searcher(&self) -> PartitionSearch<'_, D> {
+        PartitionSearch {
+            metric: self.search_metric.as_ref(),
+            search_candidates: self.search_candidates,
+            query_mode: self.query_mode,
+            storage_mode: self.storage_mode,
+            rerank_oversampling: self.rerank_oversampling,
+        }
+    }
+
+    /// Published routing state of every partition, read without locking the partitions.
+    fn partition_states(&self) -> Vec<PartitionState<D>> {
+        self.partitions
+            .iter()
+            .map(|slot| PartitionState {
+                centroid: slot.centroid(),
+                entry_count: slot.entry_count.load(Ordering::Acquire),
+                overflowed: slot.overflowed.load(Ordering::Acquire),
+            })
+            .collect()
+    }
+
+    pub fn probe_mode(&self) -> ProbeMode {
+        self.probe_mode
+    }
+
+    pub fn query_mode(&self) -> QueryMode {
+        self.query_mode
+    }
+
+    pub fn insert(&self, vector: &[f32; D], value: V, overwrite: bool) -> Result<u64, TectonicError> {
+        let result = self.store(vector, value, overwrite);
+        self.metrics.record_insert(&result);
+        result
+    }
+
+    fn store(&self, vector: &[f32; D], value: V, overwrite: bool) -> Result<u64, TectonicError> {
+        let map_id = generate_vector_key(vector);
+        let _stripe = self.insert_stripe(map_id);
+
+        // Identical vectors are resolved by their owning partition (duplicate or in-place overwrite).
+        for (idx, slot) in self.partitions.iter().enumerate() {
+            if slot.read().get_exact(vector, map_id).is_none() {
+                continue;
+            }
+            let mut partition = slot.write();
+
+            // Owner may have been removed between the shared and exclusive lock.
+            if partition.get_exact(vector, map_id).is_some() {
+                let result = partition.insert(vector, value, overwrite);
+                drop(partition);
+                if result.is_ok() {
+                    self.record_mutation(idx);
+                }
+                return result;
+            }
+            break;
+        }
+
+        // Reserve a slot, freeing one through the eviction policy when eager eviction is enabled.
+        if !self.reserve_slot() && (!self.eager_eviction || self.evict(1).is_empty() || !self.reserve_slot()) {
+            return Err(TectonicError::CacheFull { capacity: self.max_entries });
+        }
+
+        // Insert into the nearest partition that still has capacity.
+        for (_, idx) in self.searcher().rank_partitions(vector, &self.partition_states(), false) {
+            let slot = &self.partitions[idx];
+            let mut partition = slot.write();
+            if partition.entry_count >= partition.max_entries {
+                // Queries probing this partition follow the vector on to a farther one.
+                partition.overflowed = true;
+                slot.overflowed.store(true, Ordering::Release);
+                continue;
+            }
+
+            let result = partition.insert(vector, value, overwrite);
+            slot.entry_count.store(partition.entry_count, Ordering::Release);
+            drop(partition);
+
+            match result {
+                Ok(_) => self.record_mutation(idx),
+                Err(_) => {
+                    self.entry_count.fetch_sub(1, Ordering::AcqRel);
+                }
+            }
+            return result;
+        }
+
+        // Concurrent removals and inserts shifted capacity while partitions were scanned.
+        self.entry_count.fetch_sub(1, Ordering::AcqRel);
+        Err(TectonicError::CacheFull { capacity: self.max_entries })
+    }
+
+    fn insert_stripe(&self, map_id: u64) -> MutexGuard<'_, ()> {
+        self.insert_locks[(map_id % self.insert_locks.len() as u64) as usize]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn reserve_slot(&self) -> bool {
+        self.entry_count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| (count < self.max_entries).then_some(count + 1))
+            .is_ok()
+    }
+
+    fn record_mutation(&self, idx: usize) {
+        let slot = &self.partitions[idx];
+        let due = slot.mutations.fetch_add(1, Ordering::AcqRel) + 1 >= self.centroid_update;
+        let incremental = self.centroid_strategy == CentroidStrategy::Incremental;
+
+        // Publish on every incremental update, on the periodic threshold, or to seed routing.
+        if !(due || incremental || slot.centroid().is_none()) {
+            return;
+        }
+        if due {
+            slot.mutations.store(0, Ordering::Release);
+        }
+
+        // Recalculation only needs a shared lock, so concurrent queries proceed.
+        let centroid = {
+            let partition = slot.read();
+            if incremental { partition.centroid } else { partition.compute_centroid() }
+        };
+        slot.publish_centroid(centroid);
+    }
+
+    pub fn remove(&self, id: u64) -> Result<VectorEntry<D, V>, TectonicError> {
+        let idx = self.owning_partition(id).ok_or(TectonicError::EntryNotFound { entry_id: id })?;
+        let slot = &self.partitions[idx];
+
+        let removed = {
+            let mut partition = slot.write();
+            let removed = partition.remove(id);
+            slot.entry_count.store(partition.entry_count, Ordering::Release);
+            removed
+        }
+        .ok_or(TectonicError::EntryNotFound { entry_id: id })?;
+
+        self.entry_count.fetch_sub(1, Ordering::AcqRel);
+        self.record_mutation(idx);
+        Ok(removed)
+    }
+
+    /// Access statistics (hits, recency, latency) of a stored entry.
+    pub fn entry_stats(&self, id: u64) -> Option<EntryStats> {
+        self.owning_partition(id).and_then(|idx| self.partitions[idx].read().entry_stats(id))
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        self.owning_partition(id)
+            .is_some_and(|idx| self.partitions[idx].read().contains(id))
+    }
+
+    fn owning_partition(&self, id: u64) -> Option<usize> {
+        // Entry IDs embed the ID of the partition that issued them.
+        let (partition_id, _) = decode_vector_unique_id(id);
+        self.partitions.iter().position(|slot| slot.partition_id == partition_id)
+    }
+
+    pub fn evict(&self, count: usize) -> Vec<u64> {
+        // Base Case -> Nothing to evict.
+        if count == 0 || self.size() == 0 {
+            return Vec::new();
+        }
+
+        // Rank victims under shared locks, taken in partition order.
+        let victims: Vec<u64> = {
+            let centroids: Vec<Option<[f32; D]>> = self.partitions.iter().map(|slot| slot.centroid()).collect();
+            let guards: Vec<_> = self.partitions.iter().map(|slot| slot.read()).collect();
+
+            let mut candidates = Vec::new();
+            for (partition_idx, partition) in guards.iter().enumerate() {
+                for shard in &partition.shards {
+                    for entry in &shard.entries {
+                        candidates.push(EvictionCandidate::from_entry(partition_idx, entry, centroids[partition_idx].as_ref()));
+                    }
+                }
+            }
+
+            self.eviction_strategy
+                .select_victims(&candidates, count, self.search_metric.as_ref())
+                .into_iter()
+                .map(|idx| candidates[idx].entry_id)
+                .collect()
+        };
+
+        // Victims removed concurrently by another thread are skipped.
+        let evicted: Vec<u64> = victims
+            .into_iter()
+            .filter_map(|id| self.remove(id).ok())
+            .map(|entry| entry.entry_id)
+            .collect();
+
+        self.metrics.record_evictions(evicted.len());
+        evicted
+    }
+
+    /// Snapshot of activity counters, query latencies and partition/shard fill.
+    pub fn metrics(&self) -> CacheMetrics {
+        let partitions = self.partitions.iter().map(|slot| slot.read().metrics()).collect();
+        CacheMetrics::collect(&self.cache_id, &self.metrics, self.max_entries, partitions)
+    }
+
+    pub fn partition_sizes(&self) -> Vec<usize> {
+        self.partitions.iter().map(|slot| slot.entry_count.load(Ordering::Acquire)).collect()
+    }
+
+    pub fn size(&self) -> usize {
+        self.partitions.iter().map(|slot| slot.entry_count.load(Ordering::Acquire)).sum()
+    }
+
+    pub fn factor(&self) -> f32 {
+        self.size() as f32 / self.max_entries as f32
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entry_count.load(Ordering::Acquire) >= self.max_entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn concurrent_cache(max_entries: usize) -> ConcurrentVectorCache<3, usize> {
+        ConcurrentVectorCache::builder()
+            .max_entries(max_entries)
+            .partition_count(4)
+            .shard_count(2)
+            .centroid_update(8)
+            .metric("euclidean")
+            .search_candidates(max_entries)
+            .probe_mode(ProbeMode::Exhaustive)
+            .build_concurrent()
+            .unwrap()
+    }
+
+    #[test]
+    fn concurrent_inserts_and_queries_observe_every_entry() {
+        let cache = Arc::new(concurrent_cache(256));
+
+        let handles: Vec<_> = (0..8)
+            .map(|worker| {
+                let cache = Arc::clone(&cache);
+                thread::spawn(move || {
+                    let mut ids = Vec::new();
+                    for i in 0..25 {
+                        let step = worker * 25 + i;
+                        let vector = [0.0, step as f32, 255.0];
+                        ids.push(cache.insert(&vector, step, false).unwrap());
+
+                        // Readers interleave with writers on the same partitions.
+                        let hit = cache.get_similar(&vector, 1e-3).unwrap();
+                        assert_eq!(hit, Some(step));
+                    }
+                    ids
+                })
+            })
+            .collect();
+
+        let ids: Vec<u64> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+        assert_eq!(cache.size(), 200);
+        let metrics = cache.metrics();
+        assert_eq!((metrics.inserts, metrics.queries, metrics.hits), (200, 200, 200));
+        assert!(ids.iter().all(|id| cache.contains(*id)));
+    }
+
+    #[test]
+    fn contended_inserts_respect_capacity_and_duplicates() {
+        let cache = Arc::new(concurrent_cache(64));
+
+        // Every worker races on the same vectors -> exactly one insert of each succeeds.
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                thread::spawn(move || {
+                    (0..100)
+                        .filter(|step| cache.insert(&[0.0, *step as f32, 255.0], *step, false).is_ok())
+                        .count()
+                })
+            })
+            .collect();
+
+        let inserted: usize = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+        assert_eq!(inserted, 64);
+        assert_eq!(cache.size(), 64);
+        assert!(cache.is_full());
+    }
+
+    #[test]
+    fn exact_lookups_only_match_identical_vectors() {
+        let cache = concurrent_cache(16);
+        let id = cache.insert(&[1.0, 2.0, 3.0], 1, false).unwrap();
+
+        assert_eq!(cache.get_exact(&[1.0, 2.0, 3.0]).unwrap().map(|hit| hit.entry.entry_id), Some(id));
+        assert!(cache.get_exact(&[2.0, 4.0, 6.0]).unwrap().is_none());
+        assert_eq!(cache.insert(&[2.0, 4.0, 6.0], 2, false).map(|other| other != id), Ok(true));
+    }
+
+    #[test]
+    fn build_concurrent_requires_thread_safe() {
+        let cache = ConcurrentVectorCache::<3>::builder().thread_safe(false).build_concurrent();
+        assert!(matches!(cache, Err(TectonicError::InvalidConfig(_))));
+    }
+}
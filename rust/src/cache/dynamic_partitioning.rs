@@ -0,0 +1,59 @@
+
+/// Thresholds controlling automatic partition splitting and merging.
+///
+/// A partition whose fill ratio reaches `split_fill` is split in two with 2-means
+/// on its entries, each half inheriting the parent's capacity so hot regions of the
+/// embedding space gain room. Neighbouring partitions whose combined fill drops to
+/// `merge_fill` are merged back together. Total entries remain bounded by the cache
+/// `max_entries`. Relocated entries keep their IDs, which the cache resolves to the new partition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DynamicPartitioning {
+    /// Fill ratio (entries / capacity) at which a partition is split.
+    pub split_fill: f32,
+
+    /// Mean squared distance to the centroid above which a partition is split
+    /// during an explicit rebalance pass (None disables the variance check).
+    pub split_variance: Option<f32>,
+
+    /// Combined fill ratio at or below which two neighbouring partitions are merged.
+    pub merge_fill: f32,
+
+    /// Lower bound on the number of partitions.
+    pub min_partitions: usize,
+
+    /// Upper bound on the number of partitions.
+    pub max_partitions: usize,
+}
+
+impl Default for DynamicPartitioning {
+    fn default() -> Self {
+        Self {
+            split_fill: 0.9,
+            split_variance: None,
+            merge_fill: 0.25,
+            min_partitions: 1,
+            max_partitions: 64,
+        }
+    }
+}
+
+/// Summary of partition splits and merges performed by a rebalance.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RebalanceReport {
+    /// Number of partitions split in two.
+    pub splits: usize,
+
+    /// Number of partition pairs merged into one.
+    pub merges: usize,
+
+    /// IDs of vectors moved to another partition, which stay valid in their new partition.
+    pub relocated: Vec<u64>,
+}
+
+impl RebalanceReport {
+    pub fn absorb(&mut self, other: RebalanceReport) {
+        self.splits += other.splits;
+        self.merges += other.merges;
+        self.relocated.extend(other.relocated);
+    }
+}
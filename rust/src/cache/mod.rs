@@ -1,3 +1,9 @@
 pub mod vector_cache;
+pub mod vector_cache_builder;
+pub mod concurrent_vector_cache;
 pub mod cache_partition;
-pub mod cache_shard;
\ No newline at end of file
+pub mod cache_shard;
+pub mod centroid_strategy;
+pub mod dynamic_partitioning;
+pub mod partition_search;
+pub mod rebuild_report;
\ No newline at end of file
@@ -0,0 +1,150 @@
+use crate::cache::cache_partition::CachePartition;
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::search::probe_mode::ProbeMode;
+use crate::search::query_mode::QueryMode;
+use crate::search::rerank::rerank;
+use crate::search::top_k_heap::TopKHeap;
+use crate::utility::vector_utils::binary_quantize;
+use crate::vector::stored_vector::StorageMode;
+use crate::vector::vector_entry::VectorEntry;
+use std::time::Instant;
+
+/* ==============================
+    * Partition Search
+    *
+    * Query logic shared by VectorCache and ConcurrentVectorCache. Each cache
+    * snapshots the routing state of its partitions and decides how to reach
+    * the probed ones (plain borrows, or read guards taken in ascending index
+    * order), while ranking, scanning, re-ranking, exact lookups and hit
+    * recording run through the one PartitionSearch implementation below.
+============================== */
+
+/// Routing view of a partition, readable without scanning (or locking) it.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PartitionState<const D: usize> {
+    /// Centroid used to rank the partition, None until its first insert.
+    pub centroid: Option<[f32; D]>,
+
+    /// Number of stored vectors.
+    pub entry_count: usize,
+
+    /// Whether inserts routed to the partition overflowed into farther ones.
+    pub overflowed: bool,
+}
+
+/// Query settings borrowed from a cache for the duration of one call.
+pub(crate) struct PartitionSearch<'a, const D: usize> {
+    /// Metric scoring vectors and ranking centroids.
+    pub metric: &'a dyn DistanceMetricDyn<D>,
+
+    /// Maximum number of vectors examined per query.
+    pub search_candidates: usize,
+
+    /// Whether candidates are scored directly or prefiltered by Hamming distance.
+    pub query_mode: QueryMode,
+
+    /// Representation shards use for stored vectors.
+    pub storage_mode: StorageMode,
+
+    /// Multiple of top_k scored on quantized vectors before the f32 re-rank.
+    pub rerank_oversampling: usize,
+}
+
+impl<const D: usize> PartitionSearch<'_, D> {
+    /// Whether quantized candidates are re-scored against retained f32 vectors.
+    pub fn reranks(&self) -> bool {
+        self.storage_mode.supports_rerank() && self.rerank_oversampling > 1
+    }
+
+    fn candidate_pool(&self, top_k: usize) -> usize {
+        if self.query_mode.is_binary() {
+            self.query_mode.candidate_pool(top_k)
+        } else if self.reranks() {
+            top_k.saturating_mul(self.rerank_oversampling)
+        } else {
+            top_k
+        }
+    }
+
+    pub fn rank_partitions(&self, vector: &[f32; D], states: &[PartitionState<D>], skip_empty: bool) -> Vec<(f32, usize)> {
+        // Score every partition centroid against the vector (unset centroids rank first so they are seeded or probed).
+        let mut ranked: Vec<(f32, usize)> = states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| !skip_empty || state.entry_count > 0)
+            .map(|(idx, state)| match &state.centroid {
+                Some(centroid) => (self.metric.distance(vector, centroid), idx),
+                None => (f32::NEG_INFINITY, idx),
+            })
+            .collect();
+
+        // Stable sort keeps partition order for equidistant centroids.
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked
+    }
+
+    /// Partitions a query scans, closest first, following inserts that overflowed past full ones.
+    pub fn probes(&self, vector: &[f32; D], states: &[PartitionState<D>], probe_mode: ProbeMode) -> Vec<usize> {
+        probe_mode.select(self.rank_partitions(vector, states, true).into_iter().map(|(_, idx)| {
+            let state = &states[idx];
+            (idx, state.overflowed || state.centroid.is_none())
+        }))
+    }
+
+    /// Scan partitions in probe order, re-score prefiltered candidates and record every returned entry as a hit.
+    pub fn search<'p, V: Sync + 'p>(
+        &self,
+        partitions: impl IntoIterator<Item = &'p CachePartition<D, V>>,
+        vector: &[f32; D],
+        top_k: usize,
+        threshold: f32,
+    ) -> Vec<(f32, &'p VectorEntry<D, V>)> {
+        let started = Instant::now();
+
+        // Retain the top_k closest candidates (oversampled when re-ranking), examining at most search_candidates vectors.
+        let signature = self.query_mode.is_binary().then(|| binary_quantize(vector));
+        let mut heap = TopKHeap::new(self.candidate_pool(top_k));
+        let mut budget = self.search_candidates;
+        for partition in partitions {
+            if budget == 0 || top_k == 0 {
+                break;
+            }
+            budget -= match &signature {
+                Some(signature) => partition.scan_binary(signature, budget, &mut heap),
+                None => partition.scan(vector, self.metric, threshold, budget, &mut heap),
+            };
+        }
+
+        // Hamming and quantized first passes are re-scored with the metric.
+        let mut results = heap.into_sorted_vec();
+        if signature.is_some() || self.reranks() {
+            results = rerank(results, vector, self.metric, threshold, top_k);
+        }
+
+        // Every returned entry counts as a hit for its access statistics.
+        let elapsed = started.elapsed();
+        for (_, entry) in &results {
+            entry.data.record_access(elapsed);
+        }
+        results
+    }
+
+    /// Entry of the partition stored from exactly this vector, scored and recorded as a hit within the threshold.
+    pub fn exact_match<'p, V: Sync>(
+        &self,
+        partition: &'p CachePartition<D, V>,
+        vector: &[f32; D],
+        map_id: u64,
+        threshold: f32,
+        started: Instant,
+    ) -> Option<(f32, &'p VectorEntry<D, V>)> {
+        let entry = partition.get_exact(vector, map_id)?;
+        let score = entry.vector.exact_distance(vector, self.metric);
+
+        // Statistics are atomic, so hits are recorded under a shared borrow (or lock).
+        (score <= threshold).then(|| {
+            entry.data.record_access(started.elapsed());
+            (score, entry)
+        })
+    }
+}
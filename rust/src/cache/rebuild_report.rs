@@ -0,0 +1,13 @@
+
+/// Summary of a k-means rebuild of the cache partitions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RebuildReport {
+    /// Number of vectors reassigned to a different partition.
+    pub moved: usize,
+
+    /// Number of k-means iterations performed.
+    pub iterations: usize,
+
+    /// IDs of the reassigned vectors, which stay valid in their new partition.
+    pub relocated: Vec<u64>,
+}
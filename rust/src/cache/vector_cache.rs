@@ -1,9 +1,34 @@
 use crate::cache::cache_partition::CachePartition;
+use crate::cache::centroid_strategy::CentroidStrategy;
+use crate::cache::dynamic_partitioning::{DynamicPartitioning, RebalanceReport};
+use crate::cache::partition_search::{PartitionSearch, PartitionState};
+use crate::cache::rebuild_report::RebuildReport;
+use crate::cache::vector_cache_builder::VectorCacheBuilder;
+use crate::error::TectonicError;
+use crate::eviction::eviction_policy::{EvictionCandidate, EvictionPolicyDyn};
+use crate::eviction::lfu_strategy::LfuEviction;
+use crate::eviction::lru_strategy::LruEviction;
+use crate::eviction::random_strategy::RandomEviction;
+use crate::eviction::semantic_strategy::SemanticEviction;
+use crate::filters::bloom_filter::BloomFilter;
+use crate::filters::counting_bloom_filter::CountingBloomFilter;
+use crate::filters::cuckoo_filter::CuckooFilter;
+use crate::filters::membership_filter::MembershipFilterDyn;
+use crate::metadata::cache_metrics::CacheMetrics;
+use crate::metadata::metrics_recorder::MetricsRecorder;
+use crate::search::probe_mode::ProbeMode;
+use crate::search::query_mode::QueryMode;
+use crate::search::search_result::SearchResult;
+use crate::vector::stored_vector::StorageMode;
+use crate::vector::vector_data::EntryStats;
 use crate::vector::vector_entry::VectorEntry;
 use crate::search::distance_metric::DistanceMetricDyn;
 use crate::search::cosine_strategy::CosineProduct;
 use crate::search::euclidean_strategy::EuclideanProduct;
 use crate::search::dot_strategy::DotProduct;
+use crate::utility::kmeans::{kmeans, KMeansConfig};
+use crate::utility::random_util::XorShiftRng;
+use crate::utility::vector_utils::{decode_vector_unique_id, generate_vector_key};
 
 /* ==============================
     * Vector Cache Implementation
@@ -22,13 +47,19 @@ use crate::search::dot_strategy::DotProduct;
     * - Search metrics and candidate limits
     * - Eviction strategies (eager and approximate)
     * - Metrics collection and debug mode
+    * - Associated value payloads for semantic (key-value) caching
 ============================== */
 
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+/// Number of candidates sampled per victim when approximate eviction is enabled.
+const EVICTION_SAMPLES_PER_VICTIM: usize = 8;
+
 #[derive(Clone)]
 #[allow(dead_code)]
-pub struct VectorCache<const D: usize> {
+pub struct VectorCache<const D: usize, V = ()> {
     /// Human-readable cache idenntifier (Debugging, Metrics, Logging).
     cache_id: String,
 
@@ -47,19 +78,55 @@ pub struct VectorCache<const D: usize> {
     /// Number of actions before partition centroids are recalculated.
     centroid_update: usize,
 
+    /// Whether centroids are refreshed periodically or as a running mean.
+    centroid_strategy: CentroidStrategy,
+
     /// Flag to determine if quantization is enabled for stored vectors (Immutable).
     quantization_enabled: bool,
 
+    /// Representation shards use for stored vectors, derived from quantization_enabled (Immutable).
+    storage_mode: StorageMode,
+
+    /// Multiple of top_k scored on quantized vectors before the f32 re-rank (0 or 1 disables).
+    rerank_oversampling: usize,
+
     /// Vector distance / similarity metric utilised during queries (Immutable).
     /// (cosine, euclidean, dot-product, cosine, L2 etc.)
     search_metric: Box<dyn DistanceMetricDyn<D>>,
 
+    /// Split/merge thresholds for dynamic partitioning (None keeps partitions fixed).
+    dynamic_partitioning: Option<DynamicPartitioning>,
+
+    /// Next identifier issued to a partition created by a split.
+    next_partition_id: u64,
+
+    /// Owning partition ID of entries stored outside the partition that issued their ID (Mutable).
+    relocations: HashMap<u64, u64>,
+
+    /// Iteration cap and tolerance for k-means rebuilds.
+    kmeans_config: KMeansConfig,
+
     /// Maximum number of vectors examined per query.
     search_candidates: usize,
 
+    /// Number of partitions probed per query, ranked by centroid distance.
+    probe_mode: ProbeMode,
+
+    /// Whether candidates are scored directly or prefiltered by Hamming distance.
+    query_mode: QueryMode,
+
+    /// Number of worker threads scanning a partition's shards in parallel.
+    query_workers: usize,
+
+    /// Minimum number of vectors each worker scans before a partition is scanned in parallel.
+    parallel_threshold: usize,
+
     /// Customisable eviction strategy implemented for vector replacement.
     /// (LRU, LFU, Random, Semantic etc.)
-    eviction_strategy: String,
+    eviction_strategy: Box<dyn EvictionPolicyDyn<D>>,
+
+    /// Empty membership filter resized into every partition created by a split.
+    membership_filter: Option<Box<dyn MembershipFilterDyn>>,
 
     /// Flag to determine whether inserts are allowed to trigger immediate eviction.
     eager_eviction: bool,
@@ -73,135 +140,711 @@ pub struct VectorCache<const D: usize> {
     /// Whether to collect and expose cache performance metrics.
     metrics_enabled: bool,
 
+    /// Atomic activity counters and latency histogram (Mutable).
+    metrics: MetricsRecorder,
+
     /// Whether to enable verbose logging for debugging purposes.
     debug_mode: bool,
 
     /// Internal partitions for vector storage and management (Mutable).
-    partitions: Vec<CachePartition<D>>,
+    partitions: Vec<CachePartition<D, V>>,
+
+    /// Random source for k-means, codec training and approximate eviction sampling (Mutable).
+    rng: XorShiftRng,
 }
 
 #[allow(dead_code)]
-impl<const D: usize> VectorCache<D> {
-    fn new(
-        cache_id: String,
-        max_entries: usize,
-        partition_count: usize,
-        shard_count: usize,
-        centroid_update: usize,
-        quantization_enabled: bool,
-        search_metric: String,
-        search_candidates: usize,
-        eviction_strategy: String,
-        eager_eviction: bool,
-        approximate_eviction: bool,
-        thread_safe: bool,
-        metrics_enabled: bool,
-        debug_mode: bool,
-    ) -> Self {
-        Self {
-            cache_id,
+impl<const D: usize, V: Clone + Send + Sync> VectorCache<D, V> {
+    pub fn builder() -> VectorCacheBuilder<D, V> {
+        VectorCacheBuilder::new()
+    }
+
+    pub(crate) fn from_builder(builder: VectorCacheBuilder<D, V>) -> Result<Self, TectonicError> {
+        let partitions = Self::initialize_partitions(&builder)?;
+        let membership_filter = Self::initialise_membership_filter(&builder)?;
+        let storage_mode = builder.storage_mode();
+        let search_metric = match builder.custom_metric {
+            Some(metric) => metric,
+            None => Self::initialise_search_metric(builder.search_metric)?,
+        };
+        let eviction_strategy = match builder.custom_eviction {
+            Some(policy) => policy,
+            None => Self::initialise_eviction_policy(builder.eviction_strategy)?,
+        };
+
+        Ok(Self {
+            partitions,
+            cache_id: builder.cache_id,
             created_at: Instant::now(),
-            max_entries,
-            partition_count,
-            shard_count,
-            centroid_update,
-            quantization_enabled,
-            search_metric: Self::initialise_search_metric(search_metric),
-            search_candidates,
+            max_entries: builder.max_entries,
+            partition_count: builder.partition_count,
+            shard_count: builder.shard_count,
+            centroid_update: builder.centroid_update,
+            centroid_strategy: builder.centroid_strategy,
+            quantization_enabled: builder.quantization_enabled,
+            storage_mode,
+            rerank_oversampling: builder.rerank_oversampling,
+            search_metric,
+            dynamic_partitioning: builder.dynamic_partitioning,
+            next_partition_id: builder.partition_count as u64,
+            relocations: HashMap::new(),
+            kmeans_config: builder.kmeans_config,
+            search_candidates: builder.search_candidates,
+            probe_mode: builder.probe_mode,
+            query_mode: builder.query_mode,
+            query_workers: builder.query_workers,
+            parallel_threshold: builder.parallel_threshold,
             eviction_strategy,
-            eager_eviction,
-            approximate_eviction,
-            thread_safe,
-            metrics_enabled,
-            debug_mode,
-            partitions: Self::initialize_partitions(max_entries, partition_count, shard_count),
-        }
+            membership_filter,
+            eager_eviction: builder.eager_eviction,
+            approximate_eviction: builder.approximate_eviction,
+            thread_safe: builder.thread_safe,
+            metrics_enabled: builder.metrics_enabled,
+            metrics: MetricsRecorder::new(builder.metrics_enabled),
+            debug_mode: builder.debug_mode,
+            rng: builder.seed.map_or_else(XorShiftRng::from_entropy, XorShiftRng::new),
+        })
     }
 
-    fn calculate_partition_size(max_entries: usize, partition_count: usize) -> Vec<usize> {
+    fn calculate_partition_size(max_entries: usize, partition_count: usize) -> Result<Vec<usize>, TectonicError> {
         // Base Case -> No partitions defined.
-        assert!(partition_count > 0, "Partition count must be greater than 0");
+        if partition_count == 0 {
+            return Err(TectonicError::InvalidConfig("partition count must be greater than 0".to_string()));
+        }
 
         // Evenly distribute max_entries across partitions.
         let base = max_entries / partition_count;
         let remainder = max_entries % partition_count;
 
         // Allocate reamainders to individual partitions to ensure total matches max_entries.
-        let mut sizes = vec![base; partition_count as usize];
-        for i in 0..remainder as usize {
-            sizes[i] += 1;
+        let mut sizes = vec![base; partition_count];
+        for size in sizes.iter_mut().take(remainder) {
+            *size += 1;
         }
 
         // Return calculated partition sizes.
-        sizes
+        Ok(sizes)
     }
 
-    fn initialise_search_metric(search_metric: String) -> Box<dyn DistanceMetricDyn<D>> {
+    pub(crate) fn initialise_search_metric(search_metric: String) -> Result<Box<dyn DistanceMetricDyn<D>>, TectonicError> {
         match search_metric.to_lowercase().as_str() {
-            "cosine" => Box::new(CosineProduct),
-            "euclidean" => Box::new(EuclideanProduct),
-            "dot-product" => Box::new(DotProduct),
-            _ => panic!("Unsupported search metric: {}", search_metric),
+            "cosine" => Ok(Box::new(CosineProduct)),
+            "euclidean" => Ok(Box::new(EuclideanProduct)),
+            "dot-product" => Ok(Box::new(DotProduct)),
+            _ => Err(TectonicError::UnknownMetric(search_metric)),
         }
     }
 
-    fn initialize_partitions(max_entries: usize, partition_count: usize, shard_count: usize) -> Vec<CachePartition<D>> {
+    pub(crate) fn initialise_eviction_policy(eviction_strategy: String) -> Result<Box<dyn EvictionPolicyDyn<D>>, TectonicError> {
+        match eviction_strategy.to_lowercase().as_str() {
+            "lru" => Ok(Box::new(LruEviction)),
+            "lfu" => Ok(Box::new(LfuEviction)),
+            "random" => Ok(Box::new(RandomEviction::default())),
+            "semantic" => Ok(Box::new(SemanticEviction::default())),
+            _ => Err(TectonicError::UnknownEvictionPolicy(eviction_strategy)),
+        }
+    }
+
+    pub(crate) fn initialise_membership_filter(builder: &VectorCacheBuilder<D, V>) -> Result<Option<Box<dyn MembershipFilterDyn>>, TectonicError> {
+        let membership_filter = match (&builder.custom_filter, &builder.membership_filter) {
+            (Some(filter), _) => return Ok(Some(filter.clone())),
+            (None, None) => return Ok(None),
+            (None, Some(name)) => name,
+        };
+
+        let (expected_items, false_positive_rate) = (builder.max_entries, builder.filter_false_positive_rate);
+        match membership_filter.to_lowercase().as_str() {
+            "bloom" => Ok(Some(Box::new(BloomFilter::new(expected_items, false_positive_rate)))),
+            "counting-bloom" => Ok(Some(Box::new(CountingBloomFilter::new(expected_items, false_positive_rate)))),
+            "cuckoo" => Ok(Some(Box::new(CuckooFilter::new(expected_items)))),
+            "none" => Ok(None),
+            _ => Err(TectonicError::UnknownMembershipFilter(membership_filter.clone())),
+        }
+    }
+
+    pub(crate) fn initialize_partitions(builder: &VectorCacheBuilder<D, V>) -> Result<Vec<CachePartition<D, V>>, TectonicError> {
         // Calculate partition sizes based on total cache size and number of partitions.
-        let partition_sizes = Self::calculate_partition_size(max_entries, partition_count);
-        let mut partitions = Vec::with_capacity(partition_count as usize);
+        let partition_sizes = Self::calculate_partition_size(builder.max_entries, builder.partition_count)?;
+        let mut partitions = Vec::with_capacity(builder.partition_count);
+        let membership_filter = Self::initialise_membership_filter(builder)?;
 
         // Initialize partitions with calculated sizes, unique partition IDs, and shard counts.
         for (id, size) in partition_sizes.into_iter().enumerate() {
-            let mut partition = CachePartition::new(id as u64, size, shard_count);
-            partition.initiate_shards(size, shard_count);
+            let mut partition = CachePartition::new(id as u64, size, builder.shard_count);
+            partition.initiate_shards(size, builder.shard_count)?;
+            partition.centroid_update = builder.centroid_update;
+            partition.centroid_strategy = builder.centroid_strategy;
+            partition.query_workers = builder.query_workers;
+            partition.parallel_threshold = builder.parallel_threshold;
+            partition.membership_filter = membership_filter.as_ref().map(|filter| filter.resized(size));
+            partition.storage_mode = builder.storage_mode();
+            partition.retain_originals = builder.retains_originals();
+            partition.binary_signatures = builder.query_mode.is_binary();
             partitions.push(partition);
         }
 
         // Return initialized cache partitions.
-        partitions
+        Ok(partitions)
+    }
+
+    pub fn query(&self, vector: &[f32], top_k: usize, threshold: f32) -> Result<Vec<SearchResult<D, V>>, TectonicError> {
+        self.query_with_probe(vector, top_k, threshold, self.probe_mode)
+    }
+
+    pub fn query_with_probe(&self, vector: &[f32], top_k: usize, threshold: f32, probe_mode: ProbeMode) -> Result<Vec<SearchResult<D, V>>, TectonicError> {
+        let vector = Self::as_dimensioned(vector)?;
+        let started = self.metrics.start();
+
+        // Return matched entries ordered from most to least similar.
+        let results: Vec<SearchResult<D, V>> = self.search(vector, top_k, threshold, probe_mode)
+            .into_iter()
+            .map(|(score, entry)| SearchResult::new(entry.clone(), score))
+            .collect();
+
+        self.metrics.record_query(started, !results.is_empty());
+        Ok(results)
+    }
+
+    /// Return the payload of the most similar entry within the distance threshold.
+    /// Exact matches are answered by the partition filters without a similarity scan.
+    pub fn get_similar(&self, vector: &[f32], threshold: f32) -> Result<Option<&V>, TectonicError> {
+        let vector = Self::as_dimensioned(vector)?;
+        let started = self.metrics.start();
+
+        let value = match self.find_exact(vector, threshold) {
+            Some((_, entry)) => Some(&entry.value),
+            None => self.search(vector, 1, threshold, self.probe_mode)
+                .into_iter()
+                .next()
+                .map(|(_, entry)| &entry.value),
+        };
+
+        self.metrics.record_query(started, value.is_some());
+        Ok(value)
+    }
+
+    /// Return the entry stored from exactly this vector (bit-for-bit), probing one filter per partition.
+    pub fn get_exact(&self, vector: &[f32]) -> Result<Option<SearchResult<D, V>>, TectonicError> {
+        let vector = Self::as_dimensioned(vector)?;
+        let started = self.metrics.start();
+
+        let result = self.find_exact(vector, f32::INFINITY).map(|(score, entry)| SearchResult::new(entry.clone(), score));
+        self.metrics.record_query(started, result.is_some());
+        Ok(result)
+    }
+
+    fn find_exact(&self, vector: &[f32; D], threshold: f32) -> Option<(f32, &VectorEntry<D, V>)> {
+        let started = Instant::now();
+        let map_id = generate_vector_key(vector);
+
+        // Filters rule out partitions without touching their key maps or shards.
+        let searcher = self.searcher();
+        self.partitions
+            .iter()
+            .find_map(|partition| searcher.exact_match(partition, vector, map_id, threshold, started))
+    }
+
+    pub(crate) fn as_dimensioned(vector: &[f32]) -> Result<&[f32; D], TectonicError> {
+        // Base Case -> Query dimensions do not match the cache dimensions.
+        <&[f32; D]>::try_from(vector)
+            .map_err(|_| TectonicError::DimensionMismatch { expected: D, actual: vector.len() })
+    }
+
+    fn search(&self, vector: &[f32; D], top_k: usize, threshold: f32, probe_mode: ProbeMode) -> Vec<(f32, &VectorEntry<D, V>)> {
+        let searcher = self.searcher();
+        let probes = searcher.probes(vector, &self.partition_states(), probe_mode);
+        searcher.search(probes.into_iter().map(|idx| &self.partitions[idx]), vector, top_k, threshold)
+    }
+
+    fn searcher(&self) -> PartitionSearch<'_, D> {
+        PartitionSearch {
+            metric: self.search_metric.as_ref(),
+            search_candidates: self.search_candidates,
+            query_mode: self.query_mode,
+            storage_mode: self.storage_mode,
+            rerank_oversampling: self.rerank_oversampling,
+        }
+    }
+
+    fn partition_states(&self) -> Vec<PartitionState<D>> {
+        self.partitions
+            .iter()
+            .map(|partition| PartitionState {
+                centroid: partition.centroid,
+                entry_count: partition.entry_count,
+                overflowed: partition.overflowed,
+            })
+            .collect()
+    }
+
+    fn rank_partitions(&self, vector: &[f32; D], skip_empty: bool) -> Vec<(f32, usize)> {
+        self.searcher().rank_partitions(vector, &self.partition_states(), skip_empty)
+    }
+
+    pub fn set_probe_mode(&mut self, probe_mode: ProbeMode) {
+        self.probe_mode = probe_mode;
+    }
+
+    pub fn probe_mode(&self) -> ProbeMode {
+        self.probe_mode
+    }
+
+    /// Switch query scoring, backfilling sign codes when binary prefiltering is enabled.
+    pub fn set_query_mode(&mut self, query_mode: QueryMode) {
+        if query_mode.is_binary() {
+            self.partitions.iter_mut().for_each(|partition| partition.enable_signatures());
+        }
+        self.query_mode = query_mode;
+    }
+
+    pub fn query_mode(&self) -> QueryMode {
+        self.query_mode
+    }
+
+    pub fn insert(&mut self, vector: &[f32; D], value: V, overwrite: bool) -> Result<u64, TectonicError> {
+        let result = self.store(vector, value, overwrite);
+        self.metrics.record_insert(&result);
+        result
+    }
+
+    fn store(&mut self, vector: &[f32; D], value: V, overwrite: bool) -> Result<u64, TectonicError> {
+        // Identical vectors are resolved by their owning partition (duplicate or in-place overwrite).
+        // Partition membership filters rule out new vectors without touching the key maps.
+        let map_id = generate_vector_key(vector);
+        if let Some(owner) = self.partitions.iter_mut().find(|p| p.get_exact(vector, map_id).is_some()) {
+            return owner.insert(vector, value, overwrite);
+        }
+
+        // Free a slot through the eviction policy when eager eviction is enabled.
+        if self.is_full() && (!self.eager_eviction || self.evict(1).is_empty()) {
+            return Err(TectonicError::CacheFull { capacity: self.max_entries });
+        }
+
+        // Route the vector to the nearest partition that still has capacity.
+        let mut target_partition_idx = self.route(vector, None)?;
+
+        // Split hot partitions before inserting so the vector is routed to the matching half.
+        if self.should_split(target_partition_idx, false) {
+            self.split_partition(target_partition_idx)?;
+            target_partition_idx = self.route(vector, None)?;
+        }
+
+        self.partitions[target_partition_idx].insert(vector, value, overwrite)
+    }
+
+    /// Nearest partition with room for the vector (the current one always has room),
+    /// flagging every closer full partition as overflowed so queries follow the vector.
+    fn route(&mut self, vector: &[f32; D], current: Option<usize>) -> Result<usize, TectonicError> {
+        for (_, idx) in self.rank_partitions(vector, false) {
+            let partition = &mut self.partitions[idx];
+            if Some(idx) == current || partition.entry_count < partition.max_entries {
+                return Ok(idx);
+            }
+            partition.overflowed = true;
+        }
+        Err(TectonicError::CacheFull { capacity: self.max_entries })
+    }
+
+    pub fn remove(&mut self, id: u64) -> Result<VectorEntry<D, V>, TectonicError> {
+        let partition_idx = self.owning_partition(id).ok_or(TectonicError::EntryNotFound { entry_id: id })?;
+        let removed = self.partitions[partition_idx].remove(id).ok_or(TectonicError::EntryNotFound { entry_id: id })?;
+        self.relocations.remove(&id);
+
+        // Fold underused partitions into their nearest neighbour.
+        if let Some(neighbour_idx) = self.merge_candidate(partition_idx) {
+            self.merge_partitions(partition_idx, neighbour_idx)?;
+        }
+        Ok(removed)
+    }
+
+    /// Split and merge partitions according to the dynamic partitioning thresholds.
+    pub fn rebalance_partitions(&mut self) -> Result<RebalanceReport, TectonicError> {
+        let mut report = RebalanceReport::default();
+
+        // Split pass -> children are appended and checked in the same pass.
+        let mut idx = 0;
+        while idx < self.partitions.len() {
+            if self.should_split(idx, true) {
+                report.absorb(self.split_partition(idx)?);
+            }
+            idx += 1;
+        }
+
+        // Merge pass -> repeat until no neighbouring pair qualifies.
+        while let Some((idx, neighbour_idx)) = (0..self.partitions.len())
+            .find_map(|idx| self.merge_candidate(idx).map(|neighbour_idx| (idx, neighbour_idx)))
+        {
+            report.absorb(self.merge_partitions(idx, neighbour_idx)?);
+        }
+
+        Ok(report)
     }
 
-    pub fn query(&self, _vector: &[f32], _top_k: usize, _threshold: f32) -> Vec<VectorEntry<D>> {
-        // Placeholder for query implementation.
-        // This would involve calculating distances/similarities based on the search_metric,
-        // retrieving candidates from the relevant partitions, and returning the top_k results.
-        Vec::new()
+    fn should_split(&self, idx: usize, check_variance: bool) -> bool {
+        let Some(policy) = self.dynamic_partitioning else {
+            return false;
+        };
+        let partition = &self.partitions[idx];
+        if self.partitions.len() >= policy.max_partitions || partition.entry_count < 2 {
+            return false;
+        }
+
+        let too_dense = partition.fill_ratio() >= policy.split_fill;
+        let too_spread = check_variance
+            && policy.split_variance.is_some_and(|limit| partition.variance().is_some_and(|v| v > limit));
+        too_dense || too_spread
+    }
+
+    fn merge_candidate(&self, idx: usize) -> Option<usize> {
+        let policy = self.dynamic_partitioning?;
+        if self.partitions.len() <= policy.min_partitions.max(1) {
+            return None;
+        }
+
+        // Neighbour -> partition with the closest centroid.
+        let centroid = self.partitions[idx].centroid.unwrap_or([0.0; D]);
+        let (_, neighbour_idx) = self.rank_partitions(&centroid, false)
+            .into_iter()
+            .find(|(_, other)| *other != idx)?;
+
+        let partition = &self.partitions[idx];
+        let neighbour = &self.partitions[neighbour_idx];
+        let combined = (partition.entry_count + neighbour.entry_count) as f32;
+        let capacity = partition.max_entries.max(neighbour.max_entries) as f32;
+        (combined <= policy.merge_fill * capacity).then_some(neighbour_idx)
     }
 
-    pub fn insert(&mut self, vector: &[f32; D], overwrite: bool) -> bool {
-        assert!(!self.is_full(), "The Cache is currently full. Eviction or rebuild is required before inserting new vectors.");
+    fn split_partition(&mut self, idx: usize) -> Result<RebalanceReport, TectonicError> {
+        let mut report = RebalanceReport::default();
+        let capacity = self.partitions[idx].max_entries;
+        let shard_count = self.partitions[idx].shards.len();
+        let entries = self.partitions[idx].drain();
+
+        let decoded: Vec<Cow<'_, [f32; D]>> = entries.iter().map(|entry| entry.vector.to_f32()).collect();
+        let vectors: Vec<&[f32; D]> = decoded.iter().map(|vector| vector.as_ref()).collect();
+        let clustering = kmeans(&vectors, 2, &self.kmeans_config, self.search_metric.as_ref(), &mut self.rng);
 
-        let mut closet_distance = f32::INFINITY;
-        let mut target_partition_idx = None;
-        for (idx, partition) in self.partitions.iter().enumerate() {
-            let distance = self.search_metric.distance(vector, &partition.centroid.unwrap_or([0.0; D]));
-            if distance < closet_distance {
-                closet_distance = distance;
-                target_partition_idx = Some(idx);
+        // The larger cluster stays in place, the other moves to a new partition.
+        let stays = usize::from(clustering.assignments.iter().filter(|c| **c == 1).count() * 2 > entries.len());
+        let mut sibling = self.create_partition(capacity, shard_count)?;
+        for (entry, cluster) in entries.into_iter().zip(clustering.assignments) {
+            if cluster == stays || clustering.centroids.len() < 2 {
+                self.partitions[idx].insert_entry(entry)?;
+            } else {
+                report.relocated.push(sibling.insert_entry(entry)?);
             }
         }
 
-        let _ = self.partitions[target_partition_idx.unwrap()].insert(vector, overwrite);
-        // Placeholder for insert implementation.
-        // This would involve determining the appropriate partition for the vector,
-        // inserting it, and potentially triggering eviction if the partition is full.
-        true
+        // Base Case -> Identical vectors cannot be separated.
+        self.partitions[idx].update_centroid()?;
+        if report.relocated.is_empty() {
+            return Ok(report);
+        }
+
+        sibling.update_centroid()?;
+        sibling.overflowed = self.partitions[idx].overflowed;
+        for id in &report.relocated {
+            self.track_move(*id, sibling.partition_id);
+        }
+        self.partitions.push(sibling);
+        self.partition_count = self.partitions.len();
+        report.splits = 1;
+        Ok(report)
     }
 
-    pub fn rebuild(&mut self) {
-        // Placeholder for rebuild implementation.
-        // This would involve recalculating partition centroids, redistributing vectors,
-        // and updating any relevant metadata or membership filters.
+    fn merge_partitions(&mut self, first: usize, second: usize) -> Result<RebalanceReport, TectonicError> {
+        let mut report = RebalanceReport { merges: 1, ..RebalanceReport::default() };
+
+        // The fuller partition survives so fewer entries are tracked as relocated.
+        let (keep, absorb) = if self.partitions[first].entry_count >= self.partitions[second].entry_count {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let capacity = self.partitions[keep].max_entries.max(self.partitions[absorb].max_entries);
+        self.partitions[keep].set_capacity(capacity)?;
+        for entry in self.partitions[absorb].drain() {
+            report.relocated.push(self.partitions[keep].insert_entry(entry)?);
+        }
+        let keep_id = self.partitions[keep].partition_id;
+        for id in &report.relocated {
+            self.track_move(*id, keep_id);
+        }
+
+        self.partitions[keep].update_centroid()?;
+        self.partitions[keep].overflowed |= self.partitions[absorb].overflowed;
+        self.partitions.remove(absorb);
+        self.partition_count = self.partitions.len();
+        Ok(report)
+    }
+
+    /// Resize every partition to the given shard count, returning how many entries changed shard.
+    pub fn resize_shards(&mut self, shard_count: usize) -> Result<usize, TectonicError> {
+        // Validate against every partition first, so a rejected count never leaves the cache half resized.
+        for partition in &self.partitions {
+            partition.validate_shard_count(shard_count)?;
+        }
+
+        let mut moved = 0;
         for partition in &mut self.partitions {
-            partition.update_centroid();
+            moved += partition.resize_shards(shard_count)?;
+        }
+        self.shard_count = shard_count;
+        Ok(moved)
+    }
+
+    fn create_partition(&mut self, capacity: usize, shard_count: usize) -> Result<CachePartition<D, V>, TectonicError> {
+        let mut partition = CachePartition::new(self.next_partition_id, capacity, shard_count);
+        partition.initiate_shards(capacity, shard_count)?;
+        partition.centroid_update = self.centroid_update;
+        partition.centroid_strategy = self.centroid_strategy;
+        partition.query_workers = self.query_workers;
+        partition.parallel_threshold = self.parallel_threshold;
+        partition.membership_filter = self.membership_filter.as_ref().map(|filter| filter.resized(capacity));
+        partition.storage_mode = self.storage_mode;
+        partition.retain_originals = self.searcher().reranks();
+        partition.binary_signatures = self.query_mode.is_binary();
+        self.next_partition_id += 1;
+        Ok(partition)
+    }
+
+    /// Replace a stored vector, moving the entry when another partition is now its nearest.
+    pub fn update(&mut self, id: u64, vector: &[f32; D]) -> Result<(), TectonicError> {
+        let partition_idx = self.owning_partition(id).ok_or(TectonicError::EntryNotFound { entry_id: id })?;
+        let map_id = generate_vector_key(vector);
+
+        // Reject updates that would duplicate a different vector stored in any partition.
+        if self.partitions.iter().any(|partition| partition.get_exact(vector, map_id).is_some_and(|existing| existing.entry_id != id)) {
+            return Err(TectonicError::DuplicateEntry { key: map_id });
+        }
+        self.partitions[partition_idx].update(id, vector)?;
+
+        // Re-route like an insert, the current partition always has room for the entry it holds.
+        let target = self.route(vector, Some(partition_idx)).unwrap_or(partition_idx);
+        if target != partition_idx
+            && let Some(entry) = self.partitions[partition_idx].remove(id)
+        {
+            self.partitions[target].insert_entry(entry)?;
+            self.track_move(id, self.partitions[target].partition_id);
+        }
+        Ok(())
+    }
+
+    /// Per-partition membership filters, exposed for size and load reporting.
+    pub fn membership_filters(&self) -> impl Iterator<Item = &dyn MembershipFilterDyn> {
+        self.partitions.iter().filter_map(|partition| partition.membership_filter.as_deref())
+    }
+
+    /// Access statistics (hits, recency, latency) of a stored entry.
+    pub fn entry_stats(&self, id: u64) -> Option<EntryStats> {
+        self.owning_partition(id).and_then(|idx| self.partitions[idx].entry_stats(id))
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        self.owning_partition(id).is_some_and(|idx| self.partitions[idx].contains(id))
+    }
+
+    fn owning_partition(&self, id: u64) -> Option<usize> {
+        // Entry IDs embed the ID of the partition that issued them, unless the entry has since moved.
+        let partition_id = self.relocations.get(&id).copied().unwrap_or_else(|| decode_vector_unique_id(id).0);
+        self.partitions.iter().position(|partition| partition.partition_id == partition_id)
+    }
+
+    /// Record the partition now holding an entry, forgetting entries back in their issuing partition.
+    fn track_move(&mut self, id: u64, partition_id: u64) {
+        if decode_vector_unique_id(id).0 == partition_id {
+            self.relocations.remove(&id);
+        } else {
+            self.relocations.insert(id, partition_id);
+        }
+    }
+
+    pub fn evict(&mut self, count: usize) -> Vec<u64> {
+        // Base Case -> Nothing to evict.
+        if count == 0 || self.size() == 0 {
+            return Vec::new();
+        }
+
+        // Approximate eviction only ranks a random sample of the stored entries.
+        let sample_size = count.saturating_mul(EVICTION_SAMPLES_PER_VICTIM);
+        let candidates = if self.approximate_eviction && sample_size < self.size() {
+            Self::sample_eviction_candidates(&self.partitions, &mut self.rng, sample_size)
+        } else {
+            Self::eviction_candidates(&self.partitions)
+        };
+
+        let victims: Vec<(usize, u64)> = self.eviction_strategy
+            .select_victims(&candidates, count, self.search_metric.as_ref())
+            .into_iter()
+            .map(|idx| (candidates[idx].partition_idx, candidates[idx].entry_id))
+            .collect();
+
+        // Remove the selected victims from their owning partitions.
+        let evicted: Vec<u64> = victims
+            .into_iter()
+            .filter_map(|(partition_idx, id)| self.partitions[partition_idx].remove(id))
+            .map(|entry| entry.entry_id)
+            .collect();
+        for id in &evicted {
+            self.relocations.remove(id);
+        }
+
+        self.metrics.record_evictions(evicted.len());
+        evicted
+    }
+
+    fn eviction_candidates(partitions: &[CachePartition<D, V>]) -> Vec<EvictionCandidate<'_, D>> {
+        let mut candidates = Vec::new();
+        for (partition_idx, partition) in partitions.iter().enumerate() {
+            for shard in &partition.shards {
+                for entry in &shard.entries {
+                    candidates.push(EvictionCandidate::from_entry(partition_idx, entry, partition.centroid.as_ref()));
+                }
+            }
+        }
+        candidates
+    }
+
+    fn sample_eviction_candidates<'a>(
+        partitions: &'a [CachePartition<D, V>],
+        rng: &mut XorShiftRng,
+        sample_size: usize,
+    ) -> Vec<EvictionCandidate<'a, D>> {
+        let total: usize = partitions.iter().map(|p| p.entry_count).sum();
+        let mut seen = HashSet::with_capacity(sample_size);
+        let mut candidates = Vec::with_capacity(sample_size);
+
+        // Draw global entry positions and resolve them to partition -> shard -> entry.
+        for _ in 0..sample_size {
+            let mut position = rng.next_index(total);
+            if !seen.insert(position) {
+                continue;
+            }
+
+            'locate: for (partition_idx, partition) in partitions.iter().enumerate() {
+                if position >= partition.entry_count {
+                    position -= partition.entry_count;
+                    continue;
+                }
+                for shard in &partition.shards {
+                    if position < shard.entries.len() {
+                        let entry = &shard.entries[position];
+                        candidates.push(EvictionCandidate::from_entry(partition_idx, entry, partition.centroid.as_ref()));
+                        break 'locate;
+                    }
+                    position -= shard.entries.len();
+                }
+                break;
+            }
+        }
+        candidates
+    }
+
+    pub fn rebuild(&mut self) -> Result<RebuildReport, TectonicError> {
+        // Plan on borrowed vectors first, so a failed clustering or training step leaves every entry in place.
+        let (iterations, origins, targets, overflowed, codecs) = {
+            let mut origins = Vec::with_capacity(self.size());
+            let mut decoded: Vec<Cow<'_, [f32; D]>> = Vec::with_capacity(self.size());
+            for (partition_idx, partition) in self.partitions.iter().enumerate() {
+                for entry in partition.shards.iter().flat_map(|shard| shard.entries.iter()) {
+                    origins.push(partition_idx);
+                    decoded.push(entry.vector.to_f32());
+                }
+            }
+
+            // Cluster all stored vectors into one group per partition.
+            let vectors: Vec<&[f32; D]> = decoded.iter().map(|vector| vector.as_ref()).collect();
+            let clustering = kmeans(&vectors, self.partitions.len(), &self.kmeans_config, self.search_metric.as_ref(), &mut self.rng);
+
+            // Closest vectors claim capacity first, overflow falls back to the next-nearest centroid.
+            let mut order: Vec<(f32, usize)> = vectors
+                .iter()
+                .zip(clustering.assignments.iter())
+                .enumerate()
+                .map(|(idx, (vector, cluster))| (self.search_metric.distance(vector, &clustering.centroids[*cluster]), idx))
+                .collect();
+            order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut remaining: Vec<usize> = self.partitions.iter().map(|p| p.max_entries).collect();
+            let mut targets = vec![0; vectors.len()];
+            let mut overflowed = vec![false; self.partitions.len()];
+            for (_, idx) in order {
+                let mut ranked: Vec<(f32, usize)> = clustering.centroids
+                    .iter()
+                    .enumerate()
+                    .map(|(cluster, centroid)| (self.search_metric.distance(vectors[idx], centroid), cluster))
+                    .collect();
+                ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                // Clusters beyond the computed centroids (fewer vectors than partitions) stay empty.
+                targets[idx] = ranked
+                    .into_iter()
+                    .map(|(_, cluster)| cluster)
+                    .chain(clustering.centroids.len()..self.partitions.len())
+                    .inspect(|cluster| overflowed[*cluster] |= remaining[*cluster] == 0)
+                    .find(|cluster| remaining[*cluster] > 0)
+                    .ok_or(TectonicError::CacheFull { capacity: self.max_entries })?;
+                remaining[targets[idx]] -= 1;
+            }
+
+            // Learn each partition's codec from the vectors it receives.
+            let mut codecs = Vec::with_capacity(self.partitions.len());
+            for (idx, partition) in self.partitions.iter().enumerate() {
+                let assigned: Vec<&[f32; D]> = vectors
+                    .iter()
+                    .zip(&targets)
+                    .filter(|(_, target)| **target == idx)
+                    .map(|(vector, _)| *vector)
+                    .collect();
+
+                // Base Case -> A partition without shards cannot take its share back.
+                if !assigned.is_empty() && partition.shards.is_empty() {
+                    return Err(TectonicError::EmptyPartition { partition_id: partition.partition_id });
+                }
+                codecs.push(partition.train_codec(&assigned, &self.kmeans_config, &mut self.rng)?);
+            }
+
+            (clustering.iterations, origins, targets, overflowed, codecs)
+        };
+
+        // Targets respect every partition's capacity, so reinsertion cannot fail once draining starts.
+        let mut entries = Vec::with_capacity(origins.len());
+        for (partition, codec) in self.partitions.iter_mut().zip(codecs) {
+            entries.extend(partition.drain());
+            if let Some(codec) = codec {
+                partition.install_codec(codec);
+            }
+        }
+
+        // Reinsert entries, rebalancing shards and tracking vectors that changed partition.
+        let mut report = RebuildReport { iterations, ..RebuildReport::default() };
+        for ((entry, origin), target) in entries.into_iter().zip(origins).zip(targets) {
+            let id = self.partitions[target].insert_entry(entry)?;
+            if origin != target {
+                report.moved += 1;
+                report.relocated.push(id);
+                self.track_move(id, self.partitions[target].partition_id);
+            }
+        }
+
+        // Queries follow vectors that could not fit their nearest centroid's partition.
+        for (partition, overflowed) in self.partitions.iter_mut().zip(overflowed) {
+            partition.update_centroid()?;
+            partition.overflowed = overflowed;
         }
+        Ok(report)
     }
 
-    pub fn metrics(&self) -> String {
-        // Placeholder for metrics implementation.
-        // This would involve collecting and formatting performance metrics such as hit/miss rates,
-        // average query times, eviction counts, and other relevant statistics.
-        "Metrics not implemented".to_string()
+    /// Snapshot of activity counters, query latencies and partition/shard fill.
+    pub fn metrics(&self) -> CacheMetrics {
+        let partitions = self.partitions.iter().map(|partition| partition.metrics()).collect();
+        CacheMetrics::collect(&self.cache_id, &self.metrics, self.max_entries, partitions)
+    }
+
+    /// Heap bytes held by stored vectors, reflecting the configured storage mode.
+    pub fn vector_memory(&self) -> usize {
+        self.partitions.iter().map(|partition| partition.vector_bytes()).sum()
     }
 
     pub fn partition_sizes(&self) -> Vec<usize> {
@@ -225,23 +868,851 @@ impl<const D: usize> VectorCache<D> {
     }
 }
 
-impl<const D: usize> Default for VectorCache<D> {
+impl<const D: usize, V: Clone + Send + Sync> Default for VectorCache<D, V> {
     fn default() -> Self {
-        Self::new(
-            "default_cache".to_string(),
-            1000,
-            4,
-            1,
-            100,
-            false,
-            "cosine".to_string(),
-            100,
-            "LRU".to_string(),
-            false,
-            false,
-            true,
-            true,
-            false,
-        )
-    }
-}
\ No newline at end of file
+        Self::builder()
+            .build()
+            .expect("default cache configuration is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::product_quantizer::{ProductQuantizer, ProductQuantizerConfig};
+    use crate::utility::vector_utils::{binary_quantize, hamming_distance};
+    use crate::vector::stored_vector::{HalfFormat, StoredVector};
+    use std::sync::Arc;
+
+    fn test_cache(search_candidates: usize) -> VectorCache<3> {
+        VectorCache::builder()
+            .cache_id("test_cache")
+            .max_entries(64)
+            .partition_count(2)
+            .shard_count(2)
+            .metric("euclidean")
+            .search_candidates(search_candidates)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn query_returns_closest_entries_first() {
+        let mut cache = test_cache(100);
+        cache.insert(&[0.0, 0.0, 1.0], (), false).unwrap();
+        cache.insert(&[0.0, 1.0, 0.0], (), false).unwrap();
+        cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+
+        let results = cache.query(&[0.9, 0.1, 0.0], 2, f32::INFINITY).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].entry.vector, [1.0, 0.0, 0.0]);
+        assert!(results[0].score <= results[1].score);
+    }
+
+    #[test]
+    fn query_applies_threshold_and_budget() {
+        let mut cache = test_cache(100);
+        cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        cache.insert(&[5.0, 5.0, 5.0], (), false).unwrap();
+
+        let results = cache.query(&[1.0, 0.0, 0.0], 10, 0.5).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let mut budgeted = test_cache(1);
+        budgeted.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        budgeted.insert(&[5.0, 5.0, 5.0], (), false).unwrap();
+        assert_eq!(budgeted.query(&[1.0, 0.0, 0.0], 10, f32::INFINITY).unwrap().len(), 1);
+        assert_eq!(
+            cache.query(&[1.0, 0.0], 10, f32::INFINITY).err(),
+            Some(TectonicError::DimensionMismatch { expected: 3, actual: 2 })
+        );
+    }
+
+    #[test]
+    fn query_probes_nearest_partitions() {
+        let mut cache = test_cache(100);
+        cache.partitions[0].centroid = Some([10.0, 10.0, 10.0]);
+        cache.partitions[1].centroid = Some([0.0, 0.0, 0.0]);
+        cache.insert(&[0.0, 0.0, 1.0], (), false).unwrap();
+        cache.partitions[0].insert(&[9.0, 9.0, 9.0], (), false).unwrap();
+
+        let nearest = cache.query_with_probe(&[8.0, 8.0, 8.0], 10, f32::INFINITY, ProbeMode::Nearest(1)).unwrap();
+        assert_eq!(nearest.len(), 1);
+        assert_eq!(nearest[0].entry.vector, [9.0, 9.0, 9.0]);
+
+        let exhaustive = cache.query_with_probe(&[8.0, 8.0, 8.0], 10, f32::INFINITY, ProbeMode::Exhaustive).unwrap();
+        assert_eq!(exhaustive.len(), 2);
+    }
+
+    #[test]
+    fn nearest_probe_follows_inserts_that_overflowed_full_partitions() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(8)
+            .partition_count(2)
+            .metric("euclidean")
+            .probe_mode(ProbeMode::Nearest(1))
+            .build()
+            .unwrap();
+
+        // Unset centroids are seeded first, then the nearest partition fills and spills into the other.
+        cache.insert(&[100.0, 0.0, 0.0], (), false).unwrap();
+        cache.insert(&[-100.0, 0.0, 0.0], (), false).unwrap();
+        for i in 1..=4 {
+            cache.insert(&[100.0, i as f32, 0.0], (), false).unwrap();
+        }
+        assert_eq!(cache.partition_sizes(), vec![4, 2]);
+
+        let results = cache.query(&[100.0, 4.0, 0.0], 1, 1e-6).unwrap();
+        assert_eq!(results[0].entry.vector, [100.0, 4.0, 0.0]);
+    }
+
+    #[test]
+    fn nearest_probe_scans_only_nprobe_partitions_at_capacity() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(4)
+            .partition_count(2)
+            .metric("euclidean")
+            .probe_mode(ProbeMode::Nearest(1))
+            .build()
+            .unwrap();
+
+        // Every partition is full, but each vector landed in its nearest partition.
+        for vector in [[100.0, 0.0, 0.0], [-100.0, 0.0, 0.0], [100.0, 1.0, 0.0], [-100.0, 1.0, 0.0]] {
+            cache.insert(&vector, (), false).unwrap();
+        }
+        assert!(cache.is_full());
+
+        assert_eq!(cache.query(&[100.0, 0.0, 0.0], 4, f32::INFINITY).unwrap().len(), 2);
+        let all = cache.query_with_probe(&[100.0, 0.0, 0.0], 4, f32::INFINITY, ProbeMode::Exhaustive).unwrap();
+        assert_eq!(all.len(), 4);
+    }
+
+    #[test]
+    fn exhaustive_probing_without_a_candidate_budget_recalls_every_stored_vector() {
+        let mut rng = XorShiftRng::new(7);
+        let mut cache: VectorCache<16> = VectorCache::builder().search_candidates(usize::MAX).build().unwrap();
+        let vectors: Vec<[f32; 16]> = (0..600).map(|_| std::array::from_fn(|_| rng.next_f32() * 2.0 - 1.0)).collect();
+        for vector in &vectors {
+            cache.insert(vector, (), false).unwrap();
+        }
+
+        let missed = vectors
+            .iter()
+            .filter(|vector| cache.query(&vector[..], 1, 1e-6).unwrap().first().is_none_or(|result| result.entry.vector != **vector))
+            .count();
+        assert_eq!(missed, 0);
+    }
+
+    #[test]
+    fn insert_reports_errors_instead_of_panicking() {
+        let mut cache = test_cache(100);
+        cache.insert(&[1.0, 2.0, 3.0], (), false).unwrap();
+        assert!(matches!(
+            cache.insert(&[1.0, 2.0, 3.0], (), false),
+            Err(TectonicError::DuplicateEntry { .. })
+        ));
+
+        let metric = VectorCache::<3>::initialise_search_metric("hamming".to_string());
+        assert!(matches!(metric, Err(TectonicError::UnknownMetric(_))));
+
+        for i in 0..63 {
+            cache.insert(&[0.0, i as f32, 63.0], (), false).unwrap();
+        }
+        assert_eq!(cache.insert(&[9.0, 9.0, 0.0], (), false), Err(TectonicError::CacheFull { capacity: 64 }));
+    }
+
+    #[test]
+    fn eviction_frees_space_for_inserts() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(4)
+            .partition_count(1)
+            .metric("euclidean")
+            .eviction_strategy("LRU")
+            .eager_eviction(true)
+            .build()
+            .unwrap();
+
+        let first = cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        for i in 1..4 {
+            cache.insert(&[0.0, i as f32, 1.0], (), false).unwrap();
+        }
+
+        // Eager eviction replaces the least recently used entry.
+        cache.insert(&[0.0, 0.0, 9.0], (), false).unwrap();
+        assert_eq!(cache.size(), 4);
+        let ids: Vec<u64> = cache.query(&[1.0, 0.0, 0.0], 4, f32::INFINITY).unwrap()
+            .into_iter()
+            .map(|result| result.entry.entry_id)
+            .collect();
+        assert!(!ids.contains(&first));
+
+        assert_eq!(cache.evict(2).len(), 2);
+        assert_eq!(cache.size(), 2);
+    }
+
+    #[test]
+    fn recently_read_entries_survive_eviction() {
+        for strategy in ["LRU", "LFU"] {
+            let mut cache: VectorCache<3> = VectorCache::builder()
+                .max_entries(4)
+                .partition_count(1)
+                .metric("euclidean")
+                .eviction_strategy(strategy)
+                .eager_eviction(true)
+                .build()
+                .unwrap();
+
+            let ids: Vec<u64> = (0..4)
+                .map(|i| cache.insert(&[i as f32, 1.0, 0.0], (), false).unwrap())
+                .collect();
+
+            // Similarity and exact hits both count as reads of the oldest entry.
+            assert_eq!(cache.query(&[0.1, 1.0, 0.0], 1, f32::INFINITY).unwrap()[0].entry.entry_id, ids[0]);
+            assert_eq!(cache.get_exact(&[0.0, 1.0, 0.0]).unwrap().unwrap().entry.entry_id, ids[0]);
+
+            // The oldest unread entry is replaced instead.
+            cache.insert(&[0.0, 0.0, 9.0], (), false).unwrap();
+            assert!(cache.contains(ids[0]), "{} evicted a recently read entry", strategy);
+            assert!(!cache.contains(ids[1]), "{} kept the oldest unread entry", strategy);
+            assert_eq!(cache.size(), 4);
+        }
+    }
+
+    #[test]
+    fn remove_and_update_by_id() {
+        let mut cache = test_cache(100);
+        let first = cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        let second = cache.insert(&[0.0, 1.0, 0.0], (), false).unwrap();
+        assert!(cache.contains(first));
+
+        cache.update(second, &[0.0, 0.0, 1.0]).unwrap();
+        let results = cache.query(&[0.0, 0.0, 1.0], 1, 0.1).unwrap();
+        assert_eq!(results[0].entry.entry_id, second);
+        assert!(matches!(cache.update(second, &[1.0, 0.0, 0.0]), Err(TectonicError::DuplicateEntry { .. })));
+
+        assert_eq!(cache.remove(first).unwrap().entry_id, first);
+        assert!(!cache.contains(first));
+        assert_eq!(cache.size(), 1);
+        assert_eq!(cache.remove(first).err(), Some(TectonicError::EntryNotFound { entry_id: first }));
+
+        // Removed vectors no longer count as duplicates.
+        cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+    }
+
+    #[test]
+    fn update_checks_every_partition_and_reroutes_moved_vectors() {
+        let mut cache = test_cache(100);
+        cache.partitions[0].centroid = Some([10.0, 10.0, 10.0]);
+        cache.partitions[1].centroid = Some([0.0, 0.0, 0.0]);
+        let near = cache.insert(&[0.0, 0.0, 1.0], (), false).unwrap();
+        let far = cache.insert(&[9.0, 9.0, 9.0], (), false).unwrap();
+        assert_eq!(cache.partition_sizes(), vec![1, 1]);
+
+        // The colliding vector lives in the other partition.
+        assert!(matches!(cache.update(near, &[9.0, 9.0, 9.0]), Err(TectonicError::DuplicateEntry { .. })));
+
+        cache.update(near, &[11.0, 10.0, 10.0]).unwrap();
+        assert_eq!(cache.partition_sizes(), vec![2, 0]);
+        let results = cache.query_with_probe(&[11.0, 10.0, 10.0], 1, 0.1, ProbeMode::Nearest(1)).unwrap();
+        assert_eq!(results[0].entry.entry_id, near);
+        assert!(cache.contains(near) && cache.contains(far));
+        assert_eq!(cache.remove(near).unwrap().vector, [11.0, 10.0, 10.0]);
+    }
+
+    #[test]
+    fn centroids_refresh_after_mutation_threshold() {
+        let mut periodic: VectorCache<3> = VectorCache::builder()
+            .max_entries(16)
+            .partition_count(1)
+            .centroid_update(3)
+            .metric("euclidean")
+            .build()
+            .unwrap();
+        periodic.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        periodic.insert(&[3.0, 2.0, 1.0], (), false).unwrap();
+        assert_eq!(periodic.partitions[0].centroid, Some([1.0, 0.0, 0.0])); // Seeded by the first insert.
+        periodic.insert(&[5.0, 1.0, 2.0], (), false).unwrap();
+        assert_eq!(periodic.partitions[0].centroid, Some([3.0, 1.0, 1.0]));
+
+        let mut incremental: VectorCache<3> = VectorCache::builder()
+            .max_entries(16)
+            .partition_count(1)
+            .centroid_update(100)
+            .centroid_strategy(CentroidStrategy::Incremental)
+            .metric("euclidean")
+            .build()
+            .unwrap();
+        let first = incremental.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        incremental.insert(&[3.0, 2.0, 1.0], (), false).unwrap();
+        assert_eq!(incremental.partitions[0].centroid, Some([2.0, 1.0, 0.5]));
+        incremental.remove(first).unwrap();
+        assert_eq!(incremental.partitions[0].centroid, Some([3.0, 2.0, 1.0]));
+    }
+
+    #[test]
+    fn rebuild_redistributes_vectors_across_partitions() {
+        let mut cache = test_cache(100);
+        for i in 0..8 {
+            let offset = i as f32 * 0.3;
+            cache.partitions[0].insert(&[1.0 + offset, 0.1, 0.0], (), false).unwrap();
+            cache.partitions[0].insert(&[-1.0 - offset, 5.0, 0.2], (), false).unwrap();
+        }
+        assert_eq!(cache.partition_sizes(), vec![16, 0]);
+
+        let report = cache.rebuild().unwrap();
+        assert_eq!(report.moved, 8);
+        assert_eq!(cache.partition_sizes(), vec![8, 8]);
+        assert!(report.relocated.iter().all(|id| cache.contains(*id)));
+
+        // Queries are now routed to the partition owning the matching cluster.
+        let results = cache.query(&[-1.0, 5.0, 0.2], 8, f32::INFINITY).unwrap();
+        assert_eq!(results.len(), 8);
+        assert!(results.iter().all(|result| result.entry.vector.to_f32()[0] < 0.0));
+
+        // A rebuild failing before reinsertion leaves every entry where it was.
+        let mut broken = test_cache(100);
+        for i in 0..8 {
+            broken.partitions[0].insert(&[1.0 + i as f32, 0.1, 0.0], (), false).unwrap();
+            broken.partitions[0].insert(&[-1.0 - i as f32, 5.0, 0.2], (), false).unwrap();
+        }
+        broken.partitions[1].shards.clear();
+        assert!(matches!(broken.rebuild(), Err(TectonicError::EmptyPartition { .. })));
+        assert_eq!(broken.partition_sizes(), vec![16, 0]);
+        assert_eq!(broken.query(&[1.0, 0.1, 0.0], 16, f32::INFINITY).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn hot_partitions_split_and_cold_partitions_merge() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(40)
+            .partition_count(1)
+            .centroid_update(1)
+            .metric("euclidean")
+            .probe_mode(ProbeMode::Exhaustive)
+            .dynamic_partitioning(DynamicPartitioning { split_fill: 0.5, merge_fill: 0.3, ..DynamicPartitioning::default() })
+            .build()
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..12 {
+            let offset = i as f32 * 0.1;
+            ids.push(cache.insert(&[2.0, offset, 0.0], (), false).unwrap());
+            ids.push(cache.insert(&[-2.0, 5.0, offset], (), false).unwrap());
+        }
+        assert!(cache.partition_sizes().len() >= 2);
+        assert_eq!(cache.size(), 24);
+        assert_eq!(cache.query(&[1.0, 0.1, 0.0], 40, f32::INFINITY).unwrap().len(), 24);
+
+        // IDs survive splits, and draining the cache (merging as it goes) can remove every one of them.
+        assert!(ids.iter().all(|id| cache.contains(*id)));
+        for id in ids.iter().skip(2) {
+            cache.remove(*id).unwrap();
+        }
+        assert_eq!(cache.partition_sizes().len(), 1);
+        assert!(ids.iter().take(2).all(|id| cache.remove(*id).is_ok()));
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[test]
+    fn shards_resize_and_spill_without_losing_entries() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(32)
+            .partition_count(1)
+            .shard_count(4)
+            .metric("euclidean")
+            .probe_mode(ProbeMode::Exhaustive)
+            .build()
+            .unwrap();
+
+        let ids: Vec<u64> = (0..32)
+            .map(|i| cache.insert(&[0.0, i as f32, 40.0], (), false).unwrap())
+            .collect();
+
+        // Every shard is full once the partition is, so spilling filled uneven homes.
+        assert!(cache.partitions[0].shards.iter().all(|shard| shard.is_full()));
+
+        cache.resize_shards(6).unwrap();
+        assert_eq!(cache.partitions[0].shards.len(), 6);
+        assert!(ids.iter().all(|id| cache.contains(*id)));
+
+        cache.resize_shards(2).unwrap();
+        assert_eq!(cache.size(), 32);
+        assert!(ids.iter().all(|id| cache.contains(*id)));
+        assert!(cache.resize_shards(0).is_err());
+
+        // A count only some partitions can hold is rejected before any partition changes.
+        let mut uneven: VectorCache<3> = VectorCache::builder().max_entries(7).partition_count(2).shard_count(2).build().unwrap();
+        assert_eq!(uneven.partitions.iter().map(|p| p.max_entries).collect::<Vec<_>>(), vec![4, 3]);
+        assert!(uneven.resize_shards(4).is_err());
+        assert!(uneven.partitions.iter().all(|partition| partition.shards.len() == 2));
+        assert_eq!(uneven.shard_count, 2);
+    }
+
+    #[test]
+    fn parallel_shard_scans_match_serial_results() {
+        let build = |workers: usize| {
+            let mut cache: VectorCache<3> = VectorCache::builder()
+                .max_entries(128)
+                .partition_count(1)
+                .shard_count(8)
+                .metric("euclidean")
+                .search_candidates(96)
+                .query_workers(workers)
+                .parallel_threshold(0)
+                .build()
+                .unwrap();
+            for i in 0..120 {
+                cache.insert(&[0.0, i as f32, 200.0], (), false).unwrap();
+            }
+            cache
+        };
+
+        let serial = build(1).query(&[0.0, 60.2, 200.0], 5, f32::INFINITY).unwrap();
+        let parallel = build(4).query(&[0.0, 60.2, 200.0], 5, f32::INFINITY).unwrap();
+
+        // Identical budgets per shard yield identical rankings.
+        assert_eq!(parallel.len(), 5);
+        let ids = |results: &[SearchResult<3>]| results.iter().map(|r| r.entry.entry_id).collect::<Vec<_>>();
+        assert_eq!(ids(&serial), ids(&parallel));
+
+        // Every spawned worker must be handed at least parallel_threshold vectors.
+        let mut partition = build(4).partitions.swap_remove(0);
+        partition.parallel_threshold = 40;
+        assert_eq!(partition.scan_workers(96), 2);
+        assert_eq!(partition.scan_workers(79), 1);
+        assert_eq!(partition.scan_workers(1_000), 4);
+    }
+
+    #[test]
+    fn bloom_filter_prechecks_duplicate_inserts() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(64)
+            .partition_count(2)
+            .metric("euclidean")
+            .membership_filter("bloom")
+            .filter_false_positive_rate(0.001)
+            .build()
+            .unwrap();
+
+        for i in 0..16 {
+            cache.insert(&[0.0, i as f32, 100.0], (), false).unwrap();
+        }
+        let duplicate = cache.insert(&[0.0, 3.0, 100.0], (), false);
+        assert!(matches!(duplicate, Err(TectonicError::DuplicateEntry { .. })));
+
+        // Scaled, shifted and constant vectors are not duplicates of each other.
+        for vector in [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [10.0, 20.0, 30.0], [0.0, 0.0, 0.0], [5.0, 5.0, 5.0]] {
+            cache.insert(&vector, (), false).unwrap();
+        }
+
+        let filters: Vec<_> = cache.membership_filters().collect();
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters.iter().map(|filter| filter.len()).sum::<usize>(), 21);
+        assert!(filters.iter().all(|filter| filter.size_bytes() > 0 && filter.false_positive_rate() < 0.001));
+    }
+
+    #[test]
+    fn deletable_filters_forget_removed_and_evicted_vectors() {
+        for name in ["counting-bloom", "cuckoo"] {
+            let mut cache: VectorCache<3> = VectorCache::builder()
+                .max_entries(16)
+                .partition_count(2)
+                .metric("euclidean")
+                .membership_filter(name)
+                .build()
+                .unwrap();
+
+            let ids: Vec<u64> = (0..16).map(|i| cache.insert(&[0.0, i as f32, 100.0], (), false).unwrap()).collect();
+            cache.remove(ids[3]).unwrap();
+            let evicted = cache.evict(2);
+
+            // Filter contents track the key maps, so removed vectors can be cached again.
+            assert_eq!(cache.membership_filters().map(|filter| filter.len()).sum::<usize>(), 13);
+            assert!(cache.insert(&[0.0, 3.0, 100.0], (), false).is_ok());
+            assert_eq!(evicted.len(), 2);
+        }
+    }
+
+    #[test]
+    fn exact_lookups_resolve_through_partition_filters() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .max_entries(64)
+            .partition_count(4)
+            .metric("euclidean")
+            .build()
+            .unwrap();
+
+        let ids: Vec<u64> = (0..32).map(|i| cache.insert(&[0.0, i as f32, 100.0], (), false).unwrap()).collect();
+        assert_eq!(cache.membership_filters().count(), 4);
+
+        let hit = cache.get_exact(&[0.0, 7.0, 100.0]).unwrap().unwrap();
+        assert_eq!(hit.entry.entry_id, ids[7]);
+        assert_eq!(hit.score, 0.0);
+        assert!(cache.get_exact(&[5.0, 0.0, 1.0]).unwrap().is_none());
+
+        // Scaled copies are near-identical after per-vector quantization but are not exact hits.
+        cache.insert(&[1.0, 2.0, 3.0], (), false).unwrap();
+        assert!(cache.get_exact(&[2.0, 4.0, 6.0]).unwrap().is_none());
+        assert_eq!(cache.get_exact(&[1.0, 2.0, 3.0]).unwrap().unwrap().score, 0.0);
+
+        cache.remove(ids[7]).unwrap();
+        assert!(cache.get_exact(&[0.0, 7.0, 100.0]).unwrap().is_none());
+    }
+
+    #[test]
+    fn metrics_track_activity_only_when_enabled() {
+        let mut cache = test_cache(64);
+        cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        cache.insert(&[0.0, 1.0, 0.0], (), false).unwrap();
+        assert!(cache.insert(&[1.0, 0.0, 0.0], (), false).is_err());
+        cache.query(&[1.0, 0.0, 0.0], 1, 0.1).unwrap();
+        cache.query(&[9.0, 9.0, 0.0], 1, 0.1).unwrap();
+        cache.evict(1);
+
+        let metrics = cache.metrics();
+        assert_eq!((metrics.queries, metrics.hits, metrics.misses), (2, 1, 1));
+        assert_eq!((metrics.inserts, metrics.duplicates, metrics.evictions), (2, 1, 1));
+        assert_eq!(metrics.size, 1);
+        assert_eq!(metrics.partitions.len(), 2);
+        assert_eq!(metrics.partitions[0].shards.len(), 2);
+        assert_eq!(metrics.query_latency.count, 2);
+        assert!(metrics.query_latency.p99().is_some());
+
+        let mut quiet: VectorCache<3> = VectorCache::builder().metrics_enabled(false).build().unwrap();
+        quiet.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        quiet.query(&[1.0, 0.0, 0.0], 1, 0.1).unwrap();
+        let metrics = quiet.metrics();
+        assert_eq!((metrics.queries, metrics.inserts, metrics.size), (0, 0, 1));
+    }
+
+    #[test]
+    fn query_hits_update_entry_stats() {
+        let mut cache = test_cache(64);
+        let hot = cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        let cold = cache.insert(&[0.0, 1.0, 0.0], (), false).unwrap();
+        let inserted_at = cache.entry_stats(hot).unwrap().last_accessed;
+
+        cache.query(&[1.0, 0.0, 0.0], 1, 0.1).unwrap();
+        cache.get_exact(&[1.0, 0.0, 0.0]).unwrap();
+
+        let stats = cache.entry_stats(hot).unwrap();
+        assert_eq!(stats.entry_id, hot);
+        assert_eq!(stats.access_count, 2);
+        assert!(stats.last_accessed >= inserted_at);
+        assert!(stats.average_latency <= stats.combined_latency);
+        assert_eq!(cache.entry_stats(cold).unwrap().access_count, 0);
+        assert!(cache.entry_stats(u64::MAX).is_none());
+    }
+
+    #[test]
+    fn quantized_storage_shrinks_vectors_and_reranks_in_f32() {
+        let build = |quantized: bool, oversampling: usize| -> VectorCache<64> {
+            VectorCache::builder()
+                .max_entries(64)
+                .partition_count(1)
+                .metric("euclidean")
+                .search_candidates(64)
+                .quantization(quantized)
+                .quantized_rerank(oversampling)
+                .build()
+                .unwrap()
+        };
+        let mut rng = XorShiftRng::new(11);
+        let vectors: Vec<[f32; 64]> = (0..48).map(|_| std::array::from_fn(|_| rng.next_f32() - 0.5)).collect();
+
+        let mut full = build(false, 0);
+        let mut quantized = build(true, 0);
+        let mut reranked = build(true, 4);
+        for vector in &vectors {
+            full.insert(vector, (), false).unwrap();
+            quantized.insert(vector, (), false).unwrap();
+            reranked.insert(vector, (), false).unwrap();
+        }
+
+        // int8 codes plus a per-vector min and scale, and the f32 source when re-ranking.
+        assert_eq!(full.vector_memory(), 48 * 64 * 4);
+        assert_eq!(quantized.vector_memory(), 48 * (64 + 8));
+        assert_eq!(reranked.vector_memory(), 48 * (64 + 8 + 64 * 4));
+
+        // Scoring on codes keeps the full-precision ranking of every stored vector's neighbours.
+        let keys = |cache: &VectorCache<64>, vector: &[f32; 64]| -> Vec<u64> {
+            cache.query(vector, 3, f32::INFINITY).unwrap().iter().map(|r| r.entry.vector_key).collect()
+        };
+        for vector in &vectors {
+            assert_eq!(keys(&quantized, vector), keys(&full, vector));
+        }
+
+        // Only the scores drift, until candidates are re-ranked in f32.
+        let expected = full.query(&vectors[5], 3, f32::INFINITY).unwrap();
+        let approximate = quantized.query(&vectors[5], 3, f32::INFINITY).unwrap();
+        let exact = reranked.query(&vectors[5], 3, f32::INFINITY).unwrap();
+        assert!(approximate[0].score < 1e-3);
+        assert!(approximate.iter().zip(&expected).any(|(result, expected)| result.score != expected.score));
+        for (result, expected) in exact.iter().zip(&expected) {
+            assert_eq!(result.entry.vector_key, expected.entry.vector_key);
+            assert_eq!(result.score, expected.score);
+        }
+    }
+
+    #[test]
+    fn int8_rebuild_calibrates_a_shared_partition_grid() {
+        let mut cache: VectorCache<4> = VectorCache::builder()
+            .max_entries(16)
+            .partition_count(1)
+            .metric("euclidean")
+            .quantization(true)
+            .build()
+            .unwrap();
+        let vectors = [[0.0, 1.0, 2.0, 3.0], [-1.0, 0.5, 0.25, 4.0], [2.0, 2.0, 2.0, 2.0], [1.0, 1.5, 0.0, -0.5]];
+        for vector in &vectors[..3] {
+            cache.insert(vector, (), false).unwrap();
+        }
+
+        cache.rebuild().unwrap();
+        let quantizer = cache.partitions[0].scalar_quantizer.clone().unwrap();
+        assert_eq!(quantizer.shared_range(), (-1.0, 5.0 / 255.0));
+
+        // Re-inserted and new entries (constant ones included) share the calibrated grid.
+        cache.insert(&vectors[3], (), false).unwrap();
+        for vector in &vectors {
+            let hit = cache.get_exact(vector).unwrap().unwrap();
+            assert!(hit.entry.vector.is_calibrated_by(&quantizer));
+            let decoded = hit.entry.vector.to_f32();
+            assert!(decoded.iter().zip(vector).all(|(x, y)| (x - y).abs() <= 2.5 / 255.0 + 1e-6));
+        }
+
+        // Vectors outside the calibrated range fall back to their own range instead of clamping.
+        let outlier = [10.0, -5.0, 0.0, 1.0];
+        cache.insert(&outlier, (), false).unwrap();
+        let hit = cache.get_exact(&outlier).unwrap().unwrap();
+        assert!(!hit.entry.vector.is_calibrated_by(&quantizer));
+        let decoded = hit.entry.vector.to_f32();
+        assert!(decoded.iter().zip(&outlier).all(|(x, y)| (x - y).abs() <= 7.5 / 255.0 + 1e-5));
+    }
+
+    #[test]
+    fn int4_storage_packs_codes_and_calibrates_on_rebuild() {
+        let mut cache: VectorCache<4> = VectorCache::builder()
+            .max_entries(16)
+            .partition_count(1)
+            .metric("euclidean")
+            .quantization(true)
+            .quantization_bits(4)
+            .build()
+            .unwrap();
+        let vectors = [[0.0, 1.0, 2.0, 3.0], [-1.0, 0.5, 0.25, 4.0], [2.0, 2.0, 2.0, 2.0]];
+        for vector in &vectors {
+            cache.insert(vector, (), false).unwrap();
+        }
+
+        // Two codes per byte plus a per-vector min and scale.
+        assert_eq!(cache.vector_memory(), 3 * (2 + 8));
+
+        cache.rebuild().unwrap();
+        let quantizer = cache.partitions[0].scalar_quantizer.clone().unwrap();
+        assert_eq!((quantizer.bits(), quantizer.shared_range()), (4, (-1.0, 5.0 / 15.0)));
+        for vector in &vectors {
+            let hit = cache.get_exact(vector).unwrap().unwrap();
+            assert!(hit.entry.vector.is_calibrated_by(&quantizer));
+            let decoded = hit.entry.vector.to_f32();
+            assert!(decoded.iter().zip(vector).all(|(x, y)| (x - y).abs() <= 2.5 / 15.0 + 1e-6));
+        }
+    }
+
+    #[test]
+    fn product_quantization_trains_codecs_on_rebuild() {
+        let build = |pq: Option<ProductQuantizerConfig>, oversampling: usize| -> VectorCache<64> {
+            VectorCache::builder()
+                .max_entries(64)
+                .partition_count(1)
+                .metric("euclidean")
+                .search_candidates(64)
+                .product_quantization(pq)
+                .quantized_rerank(oversampling)
+                .seed(5)
+                .build()
+                .unwrap()
+        };
+        let mut rng = XorShiftRng::new(11);
+        let vectors: Vec<[f32; 64]> = (0..48).map(|_| std::array::from_fn(|_| rng.next_f32() - 0.5)).collect();
+
+        let config = Some(ProductQuantizerConfig { subspaces: 16, centroids: 16 });
+        let mut full = build(None, 0);
+        let mut compact = build(config, 0);
+        let mut reranked = build(config, 8);
+        for vector in &vectors {
+            full.insert(vector, (), false).unwrap();
+            compact.insert(vector, (), false).unwrap();
+            reranked.insert(vector, (), false).unwrap();
+        }
+
+        // Vectors stay in full precision until rebuild trains the partition codecs.
+        assert_eq!(compact.vector_memory(), full.vector_memory());
+        compact.rebuild().unwrap();
+        reranked.rebuild().unwrap();
+        assert_eq!(compact.size(), vectors.len());
+
+        // One byte per subspace plus the shared codec handle, and the f32 source when re-ranking.
+        let per_entry = 16 + size_of::<Arc<ProductQuantizer<64>>>();
+        assert_eq!(compact.vector_memory(), 48 * per_entry);
+        assert_eq!(reranked.vector_memory(), 48 * (per_entry + 64 * 4));
+
+        // Re-ranking closes the recall gap left by lookup-table scoring (codecs are seeded, so recall is reproducible).
+        let recall = |cache: &VectorCache<64>| -> f32 {
+            let keys = |cache: &VectorCache<64>, vector: &[f32; 64]| -> HashSet<u64> {
+                cache.query(vector, 3, f32::INFINITY).unwrap().iter().map(|r| r.entry.vector_key).collect()
+            };
+            let found: usize = vectors.iter().map(|v| keys(cache, v).intersection(&keys(&full, v)).count()).sum();
+            found as f32 / (vectors.len() * 3) as f32
+        };
+        assert!(recall(&compact) < 0.9);
+        assert_eq!(recall(&reranked), 1.0);
+
+        let exact = reranked.query(&vectors[5], 1, f32::INFINITY).unwrap();
+        assert_eq!(exact[0].entry.vector, vectors[5]);
+        assert_eq!(exact[0].score, 0.0);
+
+        // Inserts after rebuild are encoded with the trained codebooks.
+        let extra = [0.25; 64];
+        compact.insert(&extra, (), false).unwrap();
+        let stored = compact.get_exact(&extra).unwrap().unwrap().entry.vector;
+        assert!(matches!(stored, StoredVector::Product { ref codes, .. } if codes.len() == 16));
+    }
+
+    #[test]
+    fn binary_queries_prefilter_by_hamming_distance() {
+        let build = |query_mode: QueryMode| -> VectorCache<64> {
+            VectorCache::builder()
+                .max_entries(64)
+                .partition_count(1)
+                .metric("euclidean")
+                .search_candidates(64)
+                .query_mode(query_mode)
+                .build()
+                .unwrap()
+        };
+        let mut rng = XorShiftRng::new(11);
+        let vectors: Vec<[f32; 64]> = (0..48).map(|_| std::array::from_fn(|_| rng.next_f32() - 0.5)).collect();
+
+        let mut direct = build(QueryMode::Direct);
+        let mut exhaustive = build(QueryMode::Binary { oversampling: 48 });
+        let mut prefiltered = build(QueryMode::Binary { oversampling: 2 });
+        let mut narrow = build(QueryMode::Binary { oversampling: 1 });
+        for vector in &vectors {
+            direct.insert(vector, (), false).unwrap();
+            exhaustive.insert(vector, (), false).unwrap();
+            prefiltered.insert(vector, (), false).unwrap();
+            narrow.insert(vector, (), false).unwrap();
+        }
+
+        // With the whole cache in the candidate pool, re-ranking restores the direct results.
+        let expected = direct.query(&vectors[5], 3, f32::INFINITY).unwrap();
+        let reranked = exhaustive.query(&vectors[5], 3, f32::INFINITY).unwrap();
+        for (result, expected) in reranked.iter().zip(&expected) {
+            assert_eq!(result.entry.vector_key, expected.entry.vector_key);
+            assert_eq!(result.score, expected.score);
+        }
+
+        // Only the oversampling * top_k closest sign codes reach the metric re-rank.
+        let signature = binary_quantize(&vectors[5]);
+        let mut hamming: Vec<u32> = vectors.iter().map(|v| hamming_distance(&binary_quantize(v), &signature)).collect();
+        hamming.sort_unstable();
+        let cutoff = hamming[QueryMode::Binary { oversampling: 2 }.candidate_pool(3) - 1];
+        for result in prefiltered.query(&vectors[5], 3, f32::INFINITY).unwrap() {
+            let stored = result.entry.signature.as_deref().unwrap();
+            assert!(hamming_distance(stored, &signature) <= cutoff);
+        }
+
+        // Wider pools miss fewer of the direct top-3 neighbours.
+        let missed = |cache: &VectorCache<64>| -> usize {
+            vectors
+                .iter()
+                .map(|vector| {
+                    let found = cache.query(vector, 3, f32::INFINITY).unwrap();
+                    let expected = direct.query(vector, 3, f32::INFINITY).unwrap();
+                    expected.iter().filter(|e| !found.iter().any(|f| f.entry.vector_key == e.entry.vector_key)).count()
+                })
+                .sum()
+        };
+        let (narrow_missed, prefiltered_missed) = (missed(&narrow), missed(&prefiltered));
+        assert!(narrow_missed > prefiltered_missed && prefiltered_missed > 0);
+        assert_eq!(missed(&exhaustive), 0);
+
+        // Identical sign codes survive even the narrowest pool, scored with the metric.
+        let nearest = narrow.query(&vectors[5], 1, f32::INFINITY).unwrap();
+        assert_eq!(nearest[0].entry.vector, vectors[5]);
+        assert_eq!(nearest[0].score, 0.0);
+
+        // Switching modes backfills sign codes for entries stored without them.
+        direct.set_query_mode(QueryMode::Binary { oversampling: 2 });
+        assert_eq!(direct.query(&vectors[9], 1, f32::INFINITY).unwrap()[0].entry.vector, vectors[9]);
+    }
+
+    #[test]
+    fn half_precision_storage_halves_vector_memory() {
+        let build = |format: Option<HalfFormat>| -> VectorCache<64> {
+            VectorCache::builder()
+                .max_entries(64)
+                .partition_count(1)
+                .metric("euclidean")
+                .search_candidates(64)
+                .half_precision(format)
+                .build()
+                .unwrap()
+        };
+        let mut rng = XorShiftRng::new(11);
+        let vectors: Vec<[f32; 64]> = (0..48).map(|_| std::array::from_fn(|_| rng.next_f32() - 0.5)).collect();
+
+        let mut full = build(None);
+        let mut f16 = build(Some(HalfFormat::F16));
+        let mut bf16 = build(Some(HalfFormat::BF16));
+        for vector in &vectors {
+            full.insert(vector, (), false).unwrap();
+            f16.insert(vector, (), false).unwrap();
+            bf16.insert(vector, (), false).unwrap();
+        }
+
+        assert_eq!(f16.vector_memory(), 48 * 64 * 2);
+        assert_eq!(bf16.vector_memory(), 48 * 64 * 2);
+
+        // Accumulating in f32 keeps every half-precision ranking on par with full precision.
+        for vector in &vectors {
+            let expected = full.query(vector, 3, f32::INFINITY).unwrap();
+            for cache in [&f16, &bf16] {
+                let results = cache.query(vector, 3, f32::INFINITY).unwrap();
+                for (result, expected) in results.iter().zip(&expected) {
+                    assert_eq!(result.entry.vector_key, expected.entry.vector_key);
+                    assert!((result.score - expected.score).abs() < 0.05);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_similar_returns_cached_payload() {
+        let mut cache: VectorCache<3, String> = VectorCache::builder()
+            .max_entries(16)
+            .partition_count(2)
+            .build()
+            .unwrap();
+
+        cache.insert(&[1.0, 0.0, 0.0], "east".to_string(), false).unwrap();
+        cache.insert(&[0.0, 1.0, 0.0], "north".to_string(), false).unwrap();
+
+        assert_eq!(cache.get_similar(&[0.9, 0.1, 0.0], 0.1).unwrap(), Some(&"east".to_string()));
+        assert_eq!(cache.get_similar(&[0.0, 0.0, 1.0], 0.1).unwrap(), None);
+
+        // Overwriting an identical vector replaces its payload in place.
+        let id = cache.insert(&[1.0, 0.0, 0.0], "west".to_string(), true).unwrap();
+        assert_eq!(cache.size(), 2);
+        assert_eq!(cache.query(&[1.0, 0.0, 0.0], 1, 0.1).unwrap()[0].entry.entry_id, id);
+        assert_eq!(cache.get_similar(&[1.0, 0.0, 0.0], 0.1).unwrap(), Some(&"west".to_string()));
+
+        // A scaled copy is a different vector, so overwriting stores it as a new entry.
+        let scaled = cache.insert(&[2.0, 0.0, 0.0], "far east".to_string(), true).unwrap();
+        assert_ne!(scaled, id);
+        assert_eq!(cache.size(), 3);
+        assert_eq!(cache.remove(id).unwrap().value, "west");
+        assert_eq!(cache.remove(scaled).unwrap().value, "far east");
+    }
+}
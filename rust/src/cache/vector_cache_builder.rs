@@ -0,0 +1,440 @@
+use crate::cache::centroid_strategy::CentroidStrategy;
+use crate::cache::concurrent_vector_cache::ConcurrentVectorCache;
+use crate::cache::dynamic_partitioning::DynamicPartitioning;
+use crate::cache::vector_cache::VectorCache;
+use crate::error::TectonicError;
+use crate::eviction::eviction_policy::EvictionPolicyDyn;
+use crate::filters::membership_filter::MembershipFilterDyn;
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::search::probe_mode::ProbeMode;
+use crate::search::query_mode::QueryMode;
+use crate::utility::kmeans::KMeansConfig;
+use crate::utility::product_quantizer::ProductQuantizerConfig;
+use crate::vector::stored_vector::{HalfFormat, StorageMode};
+use std::marker::PhantomData;
+use std::thread;
+
+/* ==============================
+    * Vector Cache Builder
+    *
+    * Named, validated configuration for VectorCache instances. Every setter
+    * falls back to the documented default, and build() checks the configuration
+    * invariants before any partitions or shards are allocated.
+============================== */
+
+#[derive(Clone)]
+pub struct VectorCacheBuilder<const D: usize, V = ()> {
+    pub(crate) cache_id: String,
+    pub(crate) max_entries: usize,
+    pub(crate) partition_count: usize,
+    pub(crate) shard_count: usize,
+    pub(crate) centroid_update: usize,
+    pub(crate) centroid_strategy: CentroidStrategy,
+    pub(crate) quantization_enabled: bool,
+    pub(crate) quantization_bits: u32,
+    pub(crate) rerank_oversampling: usize,
+    pub(crate) product_quantization: Option<ProductQuantizerConfig>,
+    pub(crate) half_precision: Option<HalfFormat>,
+    pub(crate) search_metric: String,
+    pub(crate) custom_metric: Option<Box<dyn DistanceMetricDyn<D>>>,
+    pub(crate) dynamic_partitioning: Option<DynamicPartitioning>,
+    pub(crate) kmeans_config: KMeansConfig,
+    pub(crate) seed: Option<u64>,
+    pub(crate) search_candidates: usize,
+    pub(crate) probe_mode: ProbeMode,
+    pub(crate) query_mode: QueryMode,
+    pub(crate) query_workers: usize,
+    pub(crate) parallel_threshold: usize,
+    pub(crate) eviction_strategy: String,
+    pub(crate) custom_eviction: Option<Box<dyn EvictionPolicyDyn<D>>>,
+    pub(crate) eager_eviction: bool,
+    pub(crate) membership_filter: Option<String>,
+    pub(crate) custom_filter: Option<Box<dyn MembershipFilterDyn>>,
+    pub(crate) filter_false_positive_rate: f64,
+    pub(crate) approximate_eviction: bool,
+    pub(crate) thread_safe: bool,
+    pub(crate) metrics_enabled: bool,
+    pub(crate) debug_mode: bool,
+    value_type: PhantomData<V>,
+}
+
+impl<const D: usize, V: Clone + Send + Sync> VectorCacheBuilder<D, V> {
+    pub fn new() -> Self {
+        Self {
+            cache_id: "default_cache".to_string(),
+            max_entries: 1000,
+            partition_count: 4,
+            shard_count: 1,
+            centroid_update: 100,
+            centroid_strategy: CentroidStrategy::default(),
+            quantization_enabled: false,
+            quantization_bits: 8,
+            rerank_oversampling: 0,
+            product_quantization: None,
+            half_precision: None,
+            search_metric: "cosine".to_string(),
+            custom_metric: None,
+            dynamic_partitioning: None,
+            kmeans_config: KMeansConfig::default(),
+            seed: None,
+            search_candidates: 100,
+            probe_mode: ProbeMode::default(),
+            query_mode: QueryMode::default(),
+            query_workers: thread::available_parallelism().map_or(1, |workers| workers.get()),
+            parallel_threshold: 8192,
+            eviction_strategy: "LRU".to_string(),
+            custom_eviction: None,
+            eager_eviction: false,
+            membership_filter: Some("cuckoo".to_string()),
+            custom_filter: None,
+            filter_false_positive_rate: 0.01,
+            approximate_eviction: false,
+            thread_safe: true,
+            metrics_enabled: true,
+            debug_mode: false,
+            value_type: PhantomData,
+        }
+    }
+
+    pub fn cache_id(mut self, cache_id: impl Into<String>) -> Self {
+        self.cache_id = cache_id.into();
+        self
+    }
+
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn partition_count(mut self, partition_count: usize) -> Self {
+        self.partition_count = partition_count;
+        self
+    }
+
+    pub fn shard_count(mut self, shard_count: usize) -> Self {
+        self.shard_count = shard_count;
+        self
+    }
+
+    pub fn centroid_update(mut self, centroid_update: usize) -> Self {
+        self.centroid_update = centroid_update;
+        self
+    }
+
+    pub fn centroid_strategy(mut self, centroid_strategy: CentroidStrategy) -> Self {
+        self.centroid_strategy = centroid_strategy;
+        self
+    }
+
+    /// Store vectors as scalar codes (int8 unless `quantization_bits` says 4) with per-vector min/scale and score queries on the codes.
+    pub fn quantization(mut self, enabled: bool) -> Self {
+        self.quantization_enabled = enabled;
+        self
+    }
+
+    /// Bits per scalar-quantized component, 8 (default) or 4 to pack two codes per byte.
+    pub fn quantization_bits(mut self, bits: u32) -> Self {
+        self.quantization_bits = bits;
+        self
+    }
+
+    /// Score `oversampling * top_k` quantized candidates, then re-rank them against f32 copies.
+    /// Keeps the f32 copy of every quantized vector; 0 or 1 disables re-ranking.
+    pub fn quantized_rerank(mut self, oversampling: usize) -> Self {
+        self.rerank_oversampling = oversampling;
+        self
+    }
+
+    /// Store vectors as f16 or bf16, halving memory while distances still accumulate in f32.
+    /// Takes precedence over scalar quantization.
+    pub fn half_precision(mut self, format: Option<HalfFormat>) -> Self {
+        self.half_precision = format;
+        self
+    }
+
+    /// Store vectors as product-quantization codes, trained per partition on rebuild.
+    /// Takes precedence over half precision and scalar quantization.
+    pub fn product_quantization(mut self, config: Option<ProductQuantizerConfig>) -> Self {
+        self.product_quantization = config;
+        self
+    }
+
+    pub(crate) fn storage_mode(&self) -> StorageMode {
+        match (self.product_quantization, self.half_precision) {
+            (Some(config), _) => StorageMode::Product(config),
+            (None, Some(format)) => StorageMode::Half(format),
+            (None, None) if self.quantization_enabled && self.quantization_bits == 4 => StorageMode::Int4,
+            (None, None) if self.quantization_enabled => StorageMode::Int8,
+            (None, None) => StorageMode::Full,
+        }
+    }
+
+    pub(crate) fn retains_originals(&self) -> bool {
+        self.storage_mode().supports_rerank() && self.rerank_oversampling > 1
+    }
+
+    /// Select a built-in metric by name (cosine, euclidean, dot-product).
+    pub fn metric(mut self, search_metric: impl Into<String>) -> Self {
+        self.search_metric = search_metric.into();
+        self.custom_metric = None;
+        self
+    }
+
+    /// Use a custom distance metric instead of a built-in one.
+    pub fn custom_metric(mut self, metric: Box<dyn DistanceMetricDyn<D>>) -> Self {
+        self.custom_metric = Some(metric);
+        self
+    }
+
+    /// Enable automatic partition splitting and merging.
+    pub fn dynamic_partitioning(mut self, thresholds: DynamicPartitioning) -> Self {
+        self.dynamic_partitioning = Some(thresholds);
+        self
+    }
+
+    /// Maximum number of k-means iterations performed by rebuild().
+    pub fn rebuild_iterations(mut self, max_iterations: usize) -> Self {
+        self.kmeans_config.max_iterations = max_iterations;
+        self
+    }
+
+    /// Largest squared centroid shift at which rebuild() considers k-means converged.
+    pub fn rebuild_tolerance(mut self, tolerance: f32) -> Self {
+        self.kmeans_config.tolerance = tolerance;
+        self
+    }
+
+    /// Seed the random source behind k-means, codec training and eviction sampling (entropy when unset).
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn search_candidates(mut self, search_candidates: usize) -> Self {
+        self.search_candidates = search_candidates;
+        self
+    }
+
+    pub fn probe_mode(mut self, probe_mode: ProbeMode) -> Self {
+        self.probe_mode = probe_mode;
+        self
+    }
+
+    /// Score candidates directly, or prefilter them by Hamming distance between sign codes.
+    pub fn query_mode(mut self, query_mode: QueryMode) -> Self {
+        self.query_mode = query_mode;
+        self
+    }
+
+    /// Number of worker threads scanning a partition's shards in parallel (1 scans serially).
+    pub fn query_workers(mut self, workers: usize) -> Self {
+        self.query_workers = workers;
+        self
+    }
+
+    /// Minimum number of vectors each worker scans before a partition's shards are scanned in parallel.
+    /// Spawning a worker costs about as much as scanning several hundred vectors serially, so smaller
+    /// thresholds only pay off for expensive metrics or very high dimensions.
+    pub fn parallel_threshold(mut self, threshold: usize) -> Self {
+        self.parallel_threshold = threshold;
+        self
+    }
+
+    /// Select a built-in eviction policy by name (LRU, LFU, Random, Semantic).
+    pub fn eviction_strategy(mut self, eviction_strategy: impl Into<String>) -> Self {
+        self.eviction_strategy = eviction_strategy.into();
+        self.custom_eviction = None;
+        self
+    }
+
+    /// Use a custom eviction policy instead of a built-in one.
+    pub fn custom_eviction(mut self, policy: Box<dyn EvictionPolicyDyn<D>>) -> Self {
+        self.custom_eviction = Some(policy);
+        self
+    }
+
+    /// Select a built-in membership filter by name (Bloom, Counting-Bloom, Cuckoo, None).
+    /// Every partition owns one, pre-checking duplicates and answering exact-match lookups.
+    pub fn membership_filter(mut self, membership_filter: impl Into<String>) -> Self {
+        self.membership_filter = Some(membership_filter.into());
+        self.custom_filter = None;
+        self
+    }
+
+    /// Use a custom membership filter instead of a built-in one.
+    pub fn custom_membership_filter(mut self, filter: Box<dyn MembershipFilterDyn>) -> Self {
+        self.custom_filter = Some(filter);
+        self
+    }
+
+    /// Target false-positive rate used to size built-in membership filters.
+    pub fn filter_false_positive_rate(mut self, rate: f64) -> Self {
+        self.filter_false_positive_rate = rate;
+        self
+    }
+
+    pub fn eager_eviction(mut self, enabled: bool) -> Self {
+        self.eager_eviction = enabled;
+        self
+    }
+
+    pub fn approximate_eviction(mut self, enabled: bool) -> Self {
+        self.approximate_eviction = enabled;
+        self
+    }
+
+    pub fn thread_safe(mut self, enabled: bool) -> Self {
+        self.thread_safe = enabled;
+        self
+    }
+
+    pub fn metrics_enabled(mut self, enabled: bool) -> Self {
+        self.metrics_enabled = enabled;
+        self
+    }
+
+    pub fn debug_mode(mut self, enabled: bool) -> Self {
+        self.debug_mode = enabled;
+        self
+    }
+
+    /// Check configuration invariants without allocating the cache.
+    pub fn validate(&self) -> Result<(), TectonicError> {
+        if D == 0 {
+            return Err(Self::invalid("vector dimension must be greater than 0"));
+        }
+        if self.max_entries == 0 {
+            return Err(Self::invalid("max_entries must be greater than 0"));
+        }
+        if self.partition_count == 0 {
+            return Err(Self::invalid("partition_count must be greater than 0"));
+        }
+        if self.shard_count == 0 {
+            return Err(Self::invalid("shard_count must be greater than 0"));
+        }
+        if self.centroid_update == 0 {
+            return Err(Self::invalid("centroid_update must be greater than 0"));
+        }
+        if !matches!(self.quantization_bits, 4 | 8) {
+            return Err(Self::invalid("quantization_bits must be 4 or 8"));
+        }
+        if self.search_candidates == 0 {
+            return Err(Self::invalid("search_candidates must be at least 1"));
+        }
+        if self.query_mode == (QueryMode::Binary { oversampling: 0 }) {
+            return Err(Self::invalid("binary query oversampling must be at least 1"));
+        }
+        if self.query_workers == 0 {
+            return Err(Self::invalid("query_workers must be at least 1"));
+        }
+        if self.kmeans_config.max_iterations == 0 {
+            return Err(Self::invalid("rebuild_iterations must be greater than 0"));
+        }
+        if self.kmeans_config.tolerance.is_nan() || self.kmeans_config.tolerance < 0.0 {
+            return Err(Self::invalid("rebuild_tolerance must be a non-negative number"));
+        }
+        if let Some(config) = &self.product_quantization {
+            config.validate(D)?;
+        }
+        if !(self.filter_false_positive_rate > 0.0 && self.filter_false_positive_rate < 1.0) {
+            return Err(Self::invalid("filter_false_positive_rate must be within (0, 1)"));
+        }
+        if self.probe_mode == ProbeMode::Nearest(0) {
+            return Err(Self::invalid("probe_mode must probe at least 1 partition"));
+        }
+
+        if let Some(dynamic) = &self.dynamic_partitioning {
+            if !(dynamic.split_fill > 0.0 && dynamic.split_fill <= 1.0) {
+                return Err(Self::invalid("split_fill must be within (0, 1]"));
+            }
+            if !(dynamic.merge_fill >= 0.0 && dynamic.merge_fill < dynamic.split_fill) {
+                return Err(Self::invalid("merge_fill must be within [0, split_fill)"));
+            }
+            if dynamic.min_partitions == 0 || dynamic.min_partitions > self.partition_count {
+                return Err(Self::invalid("min_partitions must be within [1, partition_count]"));
+            }
+            if dynamic.max_partitions < self.partition_count {
+                return Err(Self::invalid("max_partitions must be at least partition_count"));
+            }
+        }
+
+        // Smallest partition receives max_entries / partition_count slots.
+        let partition_size = self.max_entries / self.partition_count;
+        if self.shard_count > partition_size {
+            return Err(TectonicError::InvalidConfig(format!(
+                "shard_count ({}) exceeds the smallest partition size ({})",
+                self.shard_count, partition_size
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<VectorCache<D, V>, TectonicError> {
+        self.validate()?;
+        VectorCache::from_builder(self)
+    }
+
+    /// Build a cache that can be shared across threads (requires thread_safe).
+    pub fn build_concurrent(self) -> Result<ConcurrentVectorCache<D, V>, TectonicError> {
+        self.validate()?;
+        ConcurrentVectorCache::from_builder(self)
+    }
+
+    fn invalid(reason: &str) -> TectonicError {
+        TectonicError::InvalidConfig(reason.to_string())
+    }
+}
+
+impl<const D: usize, V: Clone + Send + Sync> Default for VectorCacheBuilder<D, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_rejects_invalid_configuration() {
+        let zero_partitions = VectorCache::<4>::builder().partition_count(0).build();
+        assert!(matches!(zero_partitions, Err(TectonicError::InvalidConfig(_))));
+
+        let oversharded = VectorCache::<4>::builder().max_entries(8).partition_count(4).shard_count(3).build();
+        assert!(matches!(oversharded, Err(TectonicError::InvalidConfig(_))));
+
+        let no_candidates = VectorCache::<4>::builder().search_candidates(0).build();
+        assert!(matches!(no_candidates, Err(TectonicError::InvalidConfig(_))));
+
+        let odd_bits = VectorCache::<4>::builder().quantization(true).quantization_bits(6).build();
+        assert!(matches!(odd_bits, Err(TectonicError::InvalidConfig(_))));
+
+        let no_oversampling = VectorCache::<4>::builder().query_mode(QueryMode::Binary { oversampling: 0 }).build();
+        assert!(matches!(no_oversampling, Err(TectonicError::InvalidConfig(_))));
+
+        let unknown_metric = VectorCache::<4>::builder().metric("manhattan").build();
+        assert!(matches!(unknown_metric, Err(TectonicError::UnknownMetric(_))));
+
+        let unknown_eviction = VectorCache::<4>::builder().eviction_strategy("FIFO").build();
+        assert!(matches!(unknown_eviction, Err(TectonicError::UnknownEvictionPolicy(_))));
+
+        let unknown_filter = VectorCache::<4>::builder().membership_filter("quotient").build();
+        assert!(matches!(unknown_filter, Err(TectonicError::UnknownMembershipFilter(_))));
+    }
+
+    #[test]
+    fn build_applies_named_settings() {
+        let cache = VectorCache::<4>::builder()
+            .cache_id("embeddings")
+            .max_entries(128)
+            .partition_count(8)
+            .shard_count(4)
+            .metric("euclidean")
+            .build()
+            .unwrap();
+
+        assert_eq!(cache.partition_sizes().len(), 8);
+        assert_eq!(cache.size(), 0);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/* ==============================
+    * Tectonic Error Definitions
+    *
+    * Crate-wide error type returned by every fallible cache operation, replacing
+    * panics and boolean status codes so callers can recover from failures.
+============================== */
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TectonicError {
+    /// The cache has reached its maximum number of entries.
+    CacheFull { capacity: usize },
+
+    /// The target partition has reached its maximum number of entries.
+    PartitionFull { partition_id: u64 },
+
+    /// The target shard has reached its maximum number of entries.
+    ShardFull { shard_id: u64 },
+
+    /// A bit-identical vector is already stored.
+    DuplicateEntry { key: u64 },
+
+    /// Vector length does not match the cache dimensions.
+    DimensionMismatch { expected: usize, actual: usize },
+
+    /// Search metric name is not recognised.
+    UnknownMetric(String),
+
+    /// Eviction strategy name is not recognised.
+    UnknownEvictionPolicy(String),
+
+    /// Membership filter name is not recognised.
+    UnknownMembershipFilter(String),
+
+    /// Cache configuration violates an invariant.
+    InvalidConfig(String),
+
+    /// No entry with the given ID is stored in the cache.
+    EntryNotFound { entry_id: u64 },
+
+    /// Operation requires a partition with at least one shard.
+    EmptyPartition { partition_id: u64 },
+}
+
+impl fmt::Display for TectonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TectonicError::CacheFull { capacity } => {
+                write!(f, "cache is full ({} entries), eviction or rebuild is required", capacity)
+            }
+            TectonicError::PartitionFull { partition_id } => {
+                write!(f, "partition {} is full", partition_id)
+            }
+            TectonicError::ShardFull { shard_id } => write!(f, "shard {} is full", shard_id),
+            TectonicError::DuplicateEntry { key } => {
+                write!(f, "duplicate vector entry (key {:#x})", key)
+            }
+            TectonicError::DimensionMismatch { expected, actual } => {
+                write!(f, "vector dimension mismatch: expected {}, got {}", expected, actual)
+            }
+            TectonicError::UnknownMetric(metric) => {
+                write!(f, "unsupported search metric: {}", metric)
+            }
+            TectonicError::UnknownEvictionPolicy(policy) => {
+                write!(f, "unsupported eviction strategy: {}", policy)
+            }
+            TectonicError::UnknownMembershipFilter(filter) => {
+                write!(f, "unsupported membership filter: {}", filter)
+            }
+            TectonicError::InvalidConfig(reason) => {
+                write!(f, "invalid cache configuration: {}", reason)
+            }
+            TectonicError::EntryNotFound { entry_id } => {
+                write!(f, "entry {:#x} not found", entry_id)
+            }
+            TectonicError::EmptyPartition { partition_id } => {
+                write!(f, "partition {} has no shards", partition_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TectonicError {}
@@ -0,0 +1,77 @@
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::vector::vector_data::VectorData;
+use crate::vector::vector_entry::VectorEntry;
+use std::borrow::Cow;
+
+/// Entry considered for eviction together with its owning partition context.
+pub struct EvictionCandidate<'a, const D: usize> {
+    /// Index of the partition holding the entry.
+    pub partition_idx: usize,
+
+    /// Unique identifier of the stored entry.
+    pub entry_id: u64,
+
+    /// Stored high-dimensional vector, decoded when the entry is quantized.
+    pub vector: Cow<'a, [f32; D]>,
+
+    /// Access statistics of the stored entry.
+    pub data: &'a VectorData,
+
+    /// Centroid of the owning partition, if one has been calculated.
+    pub centroid: Option<&'a [f32; D]>,
+}
+
+impl<'a, const D: usize> EvictionCandidate<'a, D> {
+    pub fn from_entry<V>(partition_idx: usize, entry: &'a VectorEntry<D, V>, centroid: Option<&'a [f32; D]>) -> Self {
+        Self {
+            partition_idx,
+            entry_id: entry.entry_id,
+            vector: entry.vector.to_f32(),
+            data: &entry.data,
+            centroid,
+        }
+    }
+}
+
+pub trait EvictionPolicy<const D: usize>: Send + Sync {
+    /// Select up to `count` victims from the candidates.
+    /// Returns candidate indices ordered from most to least evictable.
+    fn select_victims(
+        &self,
+        candidates: &[EvictionCandidate<'_, D>],
+        count: usize,
+        metric: &dyn DistanceMetricDyn<D>,
+    ) -> Vec<usize>;
+}
+
+pub trait EvictionPolicyDyn<const D: usize>: EvictionPolicy<D> {
+    fn clone_box(&self) -> Box<dyn EvictionPolicyDyn<D>>;
+}
+
+impl<const D: usize, T> EvictionPolicyDyn<D> for T where
+    T: 'static + EvictionPolicy<D> + Clone,
+{
+    fn clone_box(&self) -> Box<dyn EvictionPolicyDyn<D>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<const D: usize> Clone for Box<dyn EvictionPolicyDyn<D>> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Return the indices of the `count` candidates with the smallest keys.
+pub fn select_lowest<K: Ord, F: Fn(usize) -> K>(len: usize, count: usize, key: F) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    let count = count.min(len);
+
+    // Partition around the cut-off first so only the victims are fully sorted.
+    if count > 0 && count < len {
+        indices.select_nth_unstable_by_key(count - 1, |idx| key(*idx));
+    }
+    indices.truncate(count);
+    indices.sort_by_key(|idx| key(*idx));
+    indices
+}
@@ -0,0 +1,20 @@
+use crate::eviction::eviction_policy::{select_lowest, EvictionCandidate, EvictionPolicy};
+use crate::search::distance_metric::DistanceMetricDyn;
+
+#[derive(Clone)]
+pub struct LfuEviction;
+
+impl<const D: usize> EvictionPolicy<D> for LfuEviction {
+    fn select_victims(
+        &self,
+        candidates: &[EvictionCandidate<'_, D>],
+        count: usize,
+        _metric: &dyn DistanceMetricDyn<D>,
+    ) -> Vec<usize> {
+        // Least frequently accessed entries go first, ties broken by recency.
+        select_lowest(candidates.len(), count, |idx| {
+            let data = candidates[idx].data;
+            (data.access_count(), data.last_accessed())
+        })
+    }
+}
@@ -0,0 +1,17 @@
+use crate::eviction::eviction_policy::{select_lowest, EvictionCandidate, EvictionPolicy};
+use crate::search::distance_metric::DistanceMetricDyn;
+
+#[derive(Clone)]
+pub struct LruEviction;
+
+impl<const D: usize> EvictionPolicy<D> for LruEviction {
+    fn select_victims(
+        &self,
+        candidates: &[EvictionCandidate<'_, D>],
+        count: usize,
+        _metric: &dyn DistanceMetricDyn<D>,
+    ) -> Vec<usize> {
+        // Least recently accessed entries are evicted first.
+        select_lowest(candidates.len(), count, |idx| candidates[idx].data.last_accessed())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod eviction_policy;
+pub mod lfu_strategy;
+pub mod lru_strategy;
+pub mod random_strategy;
+pub mod semantic_strategy;
\ No newline at end of file
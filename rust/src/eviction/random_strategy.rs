@@ -0,0 +1,49 @@
+use crate::eviction::eviction_policy::{EvictionCandidate, EvictionPolicy};
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::utility::random_util::XorShiftRng;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub struct RandomEviction {
+    /// Seed advanced on every selection so consecutive calls differ (Mutable).
+    seed: AtomicU64,
+}
+
+impl RandomEviction {
+    pub fn new(seed: u64) -> Self {
+        Self { seed: AtomicU64::new(seed) }
+    }
+}
+
+impl Default for RandomEviction {
+    fn default() -> Self {
+        Self::new(XorShiftRng::from_entropy().next_u64())
+    }
+}
+
+impl Clone for RandomEviction {
+    fn clone(&self) -> Self {
+        Self::new(self.seed.load(Ordering::Relaxed))
+    }
+}
+
+impl<const D: usize> EvictionPolicy<D> for RandomEviction {
+    fn select_victims(
+        &self,
+        candidates: &[EvictionCandidate<'_, D>],
+        count: usize,
+        _metric: &dyn DistanceMetricDyn<D>,
+    ) -> Vec<usize> {
+        let seed = self.seed.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed);
+        let mut rng = XorShiftRng::new(seed);
+
+        // Partial Fisher-Yates shuffle -> the first `count` slots are the victims.
+        let mut indices: Vec<usize> = (0..candidates.len()).collect();
+        let count = count.min(indices.len());
+        for i in 0..count {
+            let j = i + rng.next_index(indices.len() - i);
+            indices.swap(i, j);
+        }
+        indices.truncate(count);
+        indices
+    }
+}
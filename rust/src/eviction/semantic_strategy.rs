@@ -0,0 +1,146 @@
+use crate::eviction::eviction_policy::{EvictionCandidate, EvictionPolicy};
+use crate::search::distance_metric::DistanceMetricDyn;
+
+/// Evicts the entries most redundant in vector space, preserving semantic coverage.
+///
+/// Redundancy is the smaller of the distance to the partition centroid and the
+/// distance to the nearest retained candidate in the same partition. Pairwise
+/// comparisons are quadratic in the candidate count, so only the candidates
+/// closest to their centroids (at most `max_candidates`, or `count` when more
+/// victims are requested) are ranked pairwise.
+#[derive(Clone)]
+pub struct SemanticEviction {
+    /// Largest candidate set compared pairwise (Immutable).
+    max_candidates: usize,
+}
+
+impl SemanticEviction {
+    pub fn new(max_candidates: usize) -> Self {
+        Self { max_candidates }
+    }
+
+    /// Closest retained pool member in the same partition as `pool[slot]`, as (distance, slot).
+    fn nearest_retained<const D: usize>(
+        candidates: &[EvictionCandidate<'_, D>],
+        pool: &[usize],
+        retained: &[bool],
+        slot: usize,
+        metric: &dyn DistanceMetricDyn<D>,
+    ) -> (f32, Option<usize>) {
+        let candidate = &candidates[pool[slot]];
+        let mut nearest = (f32::INFINITY, None);
+        for (other, idx) in pool.iter().enumerate() {
+            if other == slot || !retained[other] || candidates[*idx].partition_idx != candidate.partition_idx {
+                continue;
+            }
+            let distance = metric.distance(&candidate.vector, &candidates[*idx].vector);
+            if distance < nearest.0 {
+                nearest = (distance, Some(other));
+            }
+        }
+        nearest
+    }
+}
+
+impl Default for SemanticEviction {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl<const D: usize> EvictionPolicy<D> for SemanticEviction {
+    fn select_victims(
+        &self,
+        candidates: &[EvictionCandidate<'_, D>],
+        count: usize,
+        metric: &dyn DistanceMetricDyn<D>,
+    ) -> Vec<usize> {
+        // Distance to the partition centroid never changes during selection.
+        let centroid_distance: Vec<f32> = candidates
+            .iter()
+            .map(|candidate| match candidate.centroid {
+                Some(centroid) => metric.distance(&candidate.vector, centroid),
+                None => f32::INFINITY,
+            })
+            .collect();
+
+        // Only the candidates closest to their centroids are compared pairwise.
+        let mut pool: Vec<usize> = (0..candidates.len()).collect();
+        let limit = self.max_candidates.max(count).max(1);
+        if pool.len() > limit {
+            pool.select_nth_unstable_by(limit - 1, |a, b| centroid_distance[*a].total_cmp(&centroid_distance[*b]));
+            pool.truncate(limit);
+        }
+
+        let mut retained = vec![true; pool.len()];
+        let mut nearest: Vec<(f32, Option<usize>)> = (0..pool.len())
+            .map(|slot| Self::nearest_retained(candidates, &pool, &retained, slot, metric))
+            .collect();
+
+        // Greedily evict the most redundant entry, then refresh neighbours that pointed at it.
+        let mut victims = Vec::with_capacity(count.min(pool.len()));
+        while victims.len() < count {
+            let victim = (0..pool.len())
+                .filter(|slot| retained[*slot])
+                .min_by(|a, b| {
+                    let score_a = centroid_distance[pool[*a]].min(nearest[*a].0);
+                    let score_b = centroid_distance[pool[*b]].min(nearest[*b].0);
+                    score_a.total_cmp(&score_b)
+                });
+
+            let Some(victim) = victim else {
+                break;
+            };
+            retained[victim] = false;
+            victims.push(pool[victim]);
+
+            for slot in 0..pool.len() {
+                if retained[slot] && nearest[slot].1 == Some(victim) {
+                    nearest[slot] = Self::nearest_retained(candidates, &pool, &retained, slot, metric);
+                }
+            }
+        }
+
+        victims
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::euclidean_strategy::EuclideanProduct;
+    use crate::vector::vector_entry::VectorEntry;
+
+    #[test]
+    fn evicts_redundant_entries_before_outliers() {
+        let entries = [
+            VectorEntry::new(0, [0.0, 0.0], ()),
+            VectorEntry::new(1, [0.01, 0.0], ()),
+            VectorEntry::new(2, [0.02, 0.0], ()),
+            VectorEntry::new(3, [10.0, 10.0], ()),
+        ];
+        let candidates: Vec<EvictionCandidate<'_, 2>> = entries
+            .iter()
+            .map(|entry| EvictionCandidate {
+                partition_idx: 0,
+                entry_id: entry.entry_id,
+                vector: entry.vector.to_f32(),
+                data: &entry.data,
+                centroid: None,
+            })
+            .collect();
+
+        let victims = SemanticEviction::default().select_victims(&candidates, 2, &EuclideanProduct);
+        assert_eq!(victims.len(), 2);
+        assert!(!victims.contains(&3));
+
+        // Capped ranking keeps the candidates nearest the centroid and still spares the outlier.
+        let centroid = [0.0, 0.0];
+        let centred: Vec<EvictionCandidate<'_, 2>> = candidates
+            .into_iter()
+            .map(|candidate| EvictionCandidate { centroid: Some(&centroid), ..candidate })
+            .collect();
+        let victims = SemanticEviction::new(2).select_victims(&centred, 1, &EuclideanProduct);
+        assert_eq!(victims, vec![0]);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::filters::membership_filter::{MembershipFilter, MembershipFilterDyn};
+use crate::utility::hashing_util::hash_u64;
+use std::f64::consts::LN_2;
+
+/// Optimal (bit count, hash count) for the expected number of keys and target false-positive rate.
+pub fn bloom_parameters(expected_items: usize, false_positive_rate: f64) -> (usize, u32) {
+    let items = expected_items.max(1) as f64;
+    let rate = false_positive_rate.clamp(f64::MIN_POSITIVE, 0.5);
+
+    // m = -n ln(p) / ln(2)^2, k = (m / n) ln(2).
+    let bits = (-items * rate.ln() / (LN_2 * LN_2)).ceil().max(64.0) as usize;
+    let hashes = ((bits as f64 / items) * LN_2).round().clamp(1.0, 32.0) as u32;
+    (bits, hashes)
+}
+
+/// Bit positions probed for a key, derived by double hashing (h1 + i * h2).
+pub fn probe_positions(key: u64, hash_count: u32, slots: usize) -> impl Iterator<Item = usize> {
+    let h1 = hash_u64(key);
+    let h2 = hash_u64(h1) | 1;
+    (0..hash_count as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % slots as u64) as usize)
+}
+
+#[derive(Clone, Debug)]
+pub struct BloomFilter {
+    /// Bit table packed into 64-bit words (Mutable).
+    bits: Vec<u64>,
+
+    /// Number of addressable bits (Immutable).
+    bit_count: usize,
+
+    /// Number of bits set per key (Immutable).
+    hash_count: u32,
+
+    /// Number of keys inserted (Mutable).
+    len: usize,
+
+    /// False-positive rate the filter was sized for (Immutable).
+    target_rate: f64,
+}
+
+impl BloomFilter {
+    /// Size the filter for the expected number of keys at the target false-positive rate.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let (bit_count, hash_count) = bloom_parameters(expected_items, false_positive_rate);
+        Self { target_rate: false_positive_rate, ..Self::with_size(bit_count, hash_count) }
+    }
+
+    pub fn with_size(bit_count: usize, hash_count: u32) -> Self {
+        let bit_count = bit_count.max(1);
+        Self {
+            bits: vec![0; bit_count.div_ceil(64)],
+            bit_count,
+            hash_count: hash_count.max(1),
+            len: 0,
+            target_rate: 0.01,
+        }
+    }
+
+    pub fn bit_count(&self) -> usize {
+        self.bit_count
+    }
+
+    pub fn hash_count(&self) -> u32 {
+        self.hash_count
+    }
+}
+
+impl MembershipFilter for BloomFilter {
+    fn insert(&mut self, key: u64) -> bool {
+        for position in probe_positions(key, self.hash_count, self.bit_count) {
+            self.bits[position / 64] |= 1 << (position % 64);
+        }
+        self.len += 1;
+        true
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        probe_positions(key, self.hash_count, self.bit_count)
+            .all(|position| self.bits[position / 64] & (1 << (position % 64)) != 0)
+    }
+
+    fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0);
+        self.len = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn size_bytes(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    fn false_positive_rate(&self) -> f64 {
+        // (1 - e^(-kn/m))^k
+        let k = self.hash_count as f64;
+        (1.0 - (-k * self.len as f64 / self.bit_count as f64).exp()).powf(k)
+    }
+
+    fn resized(&self, expected_items: usize) -> Box<dyn MembershipFilterDyn> {
+        Box::new(Self::new(expected_items, self.target_rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloom_filter_has_no_false_negatives_and_bounded_false_positives() {
+        let mut filter = BloomFilter::new(1000, 0.01);
+        for key in 0..1000u64 {
+            filter.insert(key);
+        }
+
+        assert!((0..1000u64).all(|key| filter.contains(key)));
+        assert_eq!(filter.len(), 1000);
+        assert!(filter.size_bytes() >= filter.bit_count() / 8);
+
+        // Observed rate stays close to the configured target.
+        let false_positives = (1000..11_000u64).filter(|key| filter.contains(*key)).count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+        assert!(filter.false_positive_rate() < 0.02);
+
+        filter.clear();
+        assert!(filter.is_empty() && !filter.contains(7));
+    }
+}
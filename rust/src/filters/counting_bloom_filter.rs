@@ -0,0 +1,129 @@
+use crate::filters::bloom_filter::{bloom_parameters, probe_positions};
+use crate::filters::membership_filter::{MembershipFilter, MembershipFilterDyn};
+
+/// Largest value of a 4-bit counter, saturated counters are never decremented.
+const COUNTER_MAX: u8 = 0x0F;
+
+/// Bloom filter with 4-bit counters in place of bits, allowing keys to be removed.
+#[derive(Clone, Debug)]
+pub struct CountingBloomFilter {
+    /// Counters packed two per byte (Mutable).
+    counters: Vec<u8>,
+
+    /// Number of addressable counters (Immutable).
+    counter_count: usize,
+
+    /// Number of counters incremented per key (Immutable).
+    hash_count: u32,
+
+    /// Number of keys currently recorded (Mutable).
+    len: usize,
+
+    /// False-positive rate the filter was sized for (Immutable).
+    target_rate: f64,
+}
+
+impl CountingBloomFilter {
+    /// Size the filter for the expected number of keys at the target false-positive rate.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let (counter_count, hash_count) = bloom_parameters(expected_items, false_positive_rate);
+        Self {
+            counters: vec![0; counter_count.div_ceil(2)],
+            counter_count,
+            hash_count,
+            len: 0,
+            target_rate: false_positive_rate,
+        }
+    }
+
+    fn counter(&self, position: usize) -> u8 {
+        (self.counters[position / 2] >> ((position % 2) * 4)) & COUNTER_MAX
+    }
+
+    fn set_counter(&mut self, position: usize, value: u8) {
+        let shift = (position % 2) * 4;
+        let byte = &mut self.counters[position / 2];
+        *byte = (*byte & !(COUNTER_MAX << shift)) | (value << shift);
+    }
+}
+
+impl MembershipFilter for CountingBloomFilter {
+    fn insert(&mut self, key: u64) -> bool {
+        for position in probe_positions(key, self.hash_count, self.counter_count) {
+            let value = self.counter(position);
+            if value < COUNTER_MAX {
+                self.set_counter(position, value + 1);
+            }
+        }
+        self.len += 1;
+        true
+    }
+
+    fn remove(&mut self, key: u64) -> bool {
+        // Base Case -> Key was never recorded, decrementing would corrupt other keys.
+        if !self.contains(key) {
+            return false;
+        }
+
+        for position in probe_positions(key, self.hash_count, self.counter_count) {
+            let value = self.counter(position);
+            if value < COUNTER_MAX {
+                self.set_counter(position, value - 1);
+            }
+        }
+        self.len = self.len.saturating_sub(1);
+        true
+    }
+
+    fn supports_removal(&self) -> bool {
+        true
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        probe_positions(key, self.hash_count, self.counter_count).all(|position| self.counter(position) > 0)
+    }
+
+    fn clear(&mut self) {
+        self.counters.iter_mut().for_each(|byte| *byte = 0);
+        self.len = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn size_bytes(&self) -> usize {
+        self.counters.len()
+    }
+
+    fn false_positive_rate(&self) -> f64 {
+        // (1 - e^(-kn/m))^k
+        let k = self.hash_count as f64;
+        (1.0 - (-k * self.len as f64 / self.counter_count as f64).exp()).powf(k)
+    }
+
+    fn resized(&self, expected_items: usize) -> Box<dyn MembershipFilterDyn> {
+        Box::new(Self::new(expected_items, self.target_rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_keys_are_forgotten() {
+        let mut filter = CountingBloomFilter::new(500, 0.01);
+        for key in 0..500u64 {
+            filter.insert(key);
+        }
+        for key in 0..250u64 {
+            assert!(filter.remove(key));
+        }
+
+        assert_eq!(filter.len(), 250);
+        assert!((250..500u64).all(|key| filter.contains(key)));
+        let lingering = (0..250u64).filter(|key| filter.contains(*key)).count();
+        assert!(lingering < 25, "{} removed keys still reported", lingering);
+    }
+}
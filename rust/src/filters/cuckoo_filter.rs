@@ -0,0 +1,193 @@
+use crate::filters::membership_filter::{MembershipFilter, MembershipFilterDyn};
+use crate::utility::hashing_util::hash_u64;
+use crate::utility::random_util::XorShiftRng;
+
+/// Fingerprint slots per bucket.
+const BUCKET_SIZE: usize = 4;
+
+/// Relocation attempts before an insert is declared failed.
+const MAX_KICKS: usize = 500;
+
+/// Target load factor used when sizing the bucket table.
+const TARGET_LOAD: f64 = 0.9;
+
+/// Cuckoo filter (Fan et al.) storing 16-bit fingerprints in 4-way buckets.
+/// Supports deletion, with a false-positive rate of roughly 2 * 4 / 2^16 at full load.
+#[derive(Clone, Debug)]
+pub struct CuckooFilter {
+    /// Fingerprint table, 0 marks an empty slot (Mutable).
+    buckets: Vec<[u16; BUCKET_SIZE]>,
+
+    /// Fingerprint evicted by a failed relocation chain, kept so it is never lost (Mutable).
+    victim: Option<(usize, u16)>,
+
+    /// Number of fingerprints stored (Mutable).
+    len: usize,
+
+    /// Random source choosing which fingerprint to relocate (Mutable).
+    rng: XorShiftRng,
+}
+
+impl CuckooFilter {
+    /// Size the filter for the expected number of keys.
+    pub fn new(expected_items: usize) -> Self {
+        let bucket_count = ((expected_items.max(1) as f64 / (BUCKET_SIZE as f64 * TARGET_LOAD)).ceil() as usize)
+            .next_power_of_two();
+        Self {
+            buckets: vec![[0; BUCKET_SIZE]; bucket_count],
+            victim: None,
+            len: 0,
+            rng: XorShiftRng::new(bucket_count as u64),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buckets.len() * BUCKET_SIZE
+    }
+
+    /// Fingerprint and primary bucket of a key.
+    fn locate(&self, key: u64) -> (u16, usize) {
+        let hash = hash_u64(key);
+        let fingerprint = ((hash >> 48) as u16).max(1);
+        (fingerprint, (hash as usize) & (self.buckets.len() - 1))
+    }
+
+    /// Partner bucket, symmetric so either bucket leads to the other.
+    fn alternate(&self, bucket: usize, fingerprint: u16) -> usize {
+        (bucket ^ hash_u64(fingerprint as u64) as usize) & (self.buckets.len() - 1)
+    }
+
+    fn try_place(&mut self, bucket: usize, fingerprint: u16) -> bool {
+        match self.buckets[bucket].iter_mut().find(|slot| **slot == 0) {
+            Some(slot) => {
+                *slot = fingerprint;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl MembershipFilter for CuckooFilter {
+    fn insert(&mut self, key: u64) -> bool {
+        // Base Case -> A stranded fingerprint means the table is saturated.
+        if self.victim.is_some() {
+            return false;
+        }
+
+        let (mut fingerprint, first) = self.locate(key);
+        let second = self.alternate(first, fingerprint);
+        if self.try_place(first, fingerprint) || self.try_place(second, fingerprint) {
+            self.len += 1;
+            return true;
+        }
+
+        // Both buckets full -> relocate residents along a random walk.
+        let mut bucket = if self.rng.next_u64() & 1 == 0 { first } else { second };
+        for _ in 0..MAX_KICKS {
+            let slot = self.rng.next_index(BUCKET_SIZE);
+            std::mem::swap(&mut fingerprint, &mut self.buckets[bucket][slot]);
+            bucket = self.alternate(bucket, fingerprint);
+            if self.try_place(bucket, fingerprint) {
+                self.len += 1;
+                return true;
+            }
+        }
+
+        // The key is recorded, but the last displaced fingerprint waits in the victim slot.
+        self.victim = Some((bucket, fingerprint));
+        self.len += 1;
+        true
+    }
+
+    fn remove(&mut self, key: u64) -> bool {
+        let (fingerprint, first) = self.locate(key);
+        let second = self.alternate(first, fingerprint);
+
+        for bucket in [first, second] {
+            if let Some(slot) = self.buckets[bucket].iter_mut().find(|slot| **slot == fingerprint) {
+                *slot = 0;
+                self.len -= 1;
+
+                // Freed space lets the stranded fingerprint back into the table.
+                if let Some((victim_bucket, victim)) = self.victim.take() {
+                    let partner = self.alternate(victim_bucket, victim);
+                    if !(self.try_place(victim_bucket, victim) || self.try_place(partner, victim)) {
+                        self.victim = Some((victim_bucket, victim));
+                    }
+                }
+                return true;
+            }
+        }
+
+        if let Some((bucket, victim)) = self.victim
+            && victim == fingerprint
+            && (bucket == first || bucket == second)
+        {
+            self.victim = None;
+            self.len -= 1;
+            return true;
+        }
+        false
+    }
+
+    fn supports_removal(&self) -> bool {
+        true
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        let (fingerprint, first) = self.locate(key);
+        let second = self.alternate(first, fingerprint);
+
+        self.buckets[first].contains(&fingerprint)
+            || self.buckets[second].contains(&fingerprint)
+            || self.victim.is_some_and(|(bucket, victim)| victim == fingerprint && (bucket == first || bucket == second))
+    }
+
+    fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(|bucket| *bucket = [0; BUCKET_SIZE]);
+        self.victim = None;
+        self.len = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn size_bytes(&self) -> usize {
+        self.buckets.len() * std::mem::size_of::<[u16; BUCKET_SIZE]>()
+    }
+
+    fn false_positive_rate(&self) -> f64 {
+        // Each lookup compares against up to 2 * BUCKET_SIZE occupied slots.
+        let load = self.len as f64 / self.capacity() as f64;
+        1.0 - (1.0 - 1.0 / 65_535.0f64).powf(2.0 * BUCKET_SIZE as f64 * load)
+    }
+
+    fn resized(&self, expected_items: usize) -> Box<dyn MembershipFilterDyn> {
+        Box::new(Self::new(expected_items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuckoo_filter_inserts_and_removes_without_false_negatives() {
+        let mut filter = CuckooFilter::new(1000);
+        for key in 0..1000u64 {
+            assert!(filter.insert(key));
+        }
+        assert!((0..1000u64).all(|key| filter.contains(key)));
+
+        for key in (0..1000u64).step_by(2) {
+            assert!(filter.remove(key));
+        }
+        assert_eq!(filter.len(), 500);
+        assert!((1..1000u64).step_by(2).all(|key| filter.contains(key)));
+
+        let false_positives = (1000..21_000u64).filter(|key| filter.contains(*key)).count();
+        assert!(false_positives < 20, "{} false positives", false_positives);
+    }
+}
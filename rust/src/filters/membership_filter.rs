@@ -0,0 +1,58 @@
+/// Probabilistic set of vector keys (hashes of the exact f32 bits) used to skip exact-duplicate lookups.
+/// `contains` never returns false for an inserted key, but may return true for
+/// keys that were never inserted (false positives).
+pub trait MembershipFilter: Send + Sync {
+    /// Record a key as present, returns false if the filter had no room to record it.
+    fn insert(&mut self, key: u64) -> bool;
+
+    /// Forget a previously inserted key, returns false if it was not recorded.
+    /// Filters without deletion support keep reporting removed keys as present.
+    fn remove(&mut self, _key: u64) -> bool {
+        false
+    }
+
+    /// Whether remove() is able to forget keys.
+    fn supports_removal(&self) -> bool {
+        false
+    }
+
+    /// Whether the key may be present (false means definitely absent).
+    fn contains(&self, key: u64) -> bool;
+
+    /// Forget every recorded key.
+    fn clear(&mut self);
+
+    /// Number of keys recorded in the filter.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Memory occupied by the filter's table in bytes.
+    fn size_bytes(&self) -> usize;
+
+    /// Estimated false-positive probability at the current load.
+    fn false_positive_rate(&self) -> f64;
+
+    /// Empty filter of the same kind and accuracy, sized for the expected number of keys.
+    fn resized(&self, expected_items: usize) -> Box<dyn MembershipFilterDyn>;
+}
+
+pub trait MembershipFilterDyn: MembershipFilter {
+    fn clone_box(&self) -> Box<dyn MembershipFilterDyn>;
+}
+
+impl<T> MembershipFilterDyn for T where
+    T: 'static + MembershipFilter + Clone,
+{
+    fn clone_box(&self) -> Box<dyn MembershipFilterDyn> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn MembershipFilterDyn> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
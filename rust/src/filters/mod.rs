@@ -0,0 +1,4 @@
+pub mod membership_filter;
+pub mod bloom_filter;
+pub mod counting_bloom_filter;
+pub mod cuckoo_filter;
\ No newline at end of file
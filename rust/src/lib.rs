@@ -1,7 +1,11 @@
 pub mod cache;
+pub mod error;
+pub mod eviction;
 pub mod vector;
 pub mod utility;
 pub mod search;
+pub mod filters;
+pub mod metadata;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -0,0 +1,104 @@
+use crate::metadata::latency_histogram::LatencySnapshot;
+use crate::metadata::metrics_recorder::MetricsRecorder;
+
+/// Fill of a single shard at snapshot time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShardMetrics {
+    pub shard_id: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+/// Fill of a single partition and its shards at snapshot time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartitionMetrics {
+    pub partition_id: u64,
+    pub entries: usize,
+    pub capacity: usize,
+    pub shards: Vec<ShardMetrics>,
+}
+
+impl PartitionMetrics {
+    /// Ratio of stored entries to partition capacity.
+    pub fn fill_ratio(&self) -> f32 {
+        if self.capacity == 0 {
+            return 1.0;
+        }
+        self.entries as f32 / self.capacity as f32
+    }
+}
+
+/// Structured snapshot of cache activity and occupancy.
+/// Activity counters stay at zero when metrics collection is disabled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheMetrics {
+    /// Identifier of the cache the snapshot was taken from.
+    pub cache_id: String,
+
+    /// Whether activity counters and latencies were being collected.
+    pub enabled: bool,
+
+    /// Number of lookups served (query, get_similar, get_exact).
+    pub queries: u64,
+
+    /// Lookups returning at least one entry within the threshold.
+    pub hits: u64,
+
+    /// Lookups returning nothing within the threshold.
+    pub misses: u64,
+
+    /// Vectors stored or overwritten by insert().
+    pub inserts: u64,
+
+    /// Inserts rejected as duplicates of a stored vector.
+    pub duplicates: u64,
+
+    /// Entries removed by the eviction policy.
+    pub evictions: u64,
+
+    /// Number of stored entries.
+    pub size: usize,
+
+    /// Maximum number of entries.
+    pub capacity: usize,
+
+    /// Per-partition and per-shard fill.
+    pub partitions: Vec<PartitionMetrics>,
+
+    /// Lookup latency distribution.
+    pub query_latency: LatencySnapshot,
+}
+
+impl CacheMetrics {
+    /// Combine a recorder's activity counters with current occupancy.
+    pub fn collect(cache_id: &str, recorder: &MetricsRecorder, capacity: usize, partitions: Vec<PartitionMetrics>) -> Self {
+        let activity = recorder.snapshot();
+        Self {
+            cache_id: cache_id.to_string(),
+            enabled: recorder.is_enabled(),
+            queries: activity.queries,
+            hits: activity.hits,
+            misses: activity.misses,
+            inserts: activity.inserts,
+            duplicates: activity.duplicates,
+            evictions: activity.evictions,
+            size: partitions.iter().map(|partition| partition.entries).sum(),
+            capacity,
+            partitions,
+            query_latency: activity.query_latency,
+        }
+    }
+
+    /// Fraction of lookups that were hits, 0 without lookups.
+    pub fn hit_ratio(&self) -> f64 {
+        if self.queries == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.queries as f64
+    }
+
+    /// Ratio of stored entries to cache capacity.
+    pub fn fill_ratio(&self) -> f32 {
+        self.size as f32 / self.capacity as f32
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Finite bucket upper bounds double from 1µs to ~16.8s, the last bucket is unbounded.
+const FINITE_BUCKETS: usize = 25;
+const BASE_BOUND_NANOS: u64 = 1_000;
+
+/// Lock-free latency histogram with exponentially sized buckets.
+pub struct LatencyHistogram {
+    /// Observation count per bucket, non-cumulative (Mutable).
+    buckets: [AtomicU64; FINITE_BUCKETS + 1],
+
+    /// Sum of every observed latency in nanoseconds (Mutable).
+    sum_nanos: AtomicU64,
+}
+
+/// Point-in-time copy of a latency histogram.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LatencySnapshot {
+    /// (upper bound in nanoseconds, observations) per bucket, the last bound is u64::MAX.
+    pub buckets: Vec<(u64, u64)>,
+
+    /// Total number of observations.
+    pub count: u64,
+
+    /// Sum of every observed latency in nanoseconds.
+    pub sum_nanos: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            sum_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Upper bound of a bucket in nanoseconds.
+    pub fn bucket_bound(idx: usize) -> u64 {
+        if idx >= FINITE_BUCKETS { u64::MAX } else { BASE_BOUND_NANOS << idx }
+    }
+
+    pub fn record(&self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        let idx = (0..FINITE_BUCKETS)
+            .find(|idx| nanos <= Self::bucket_bound(*idx))
+            .unwrap_or(FINITE_BUCKETS);
+
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        let buckets: Vec<(u64, u64)> = self.buckets
+            .iter()
+            .enumerate()
+            .map(|(idx, count)| (Self::bucket_bound(idx), count.load(Ordering::Relaxed)))
+            .collect();
+
+        LatencySnapshot {
+            count: buckets.iter().map(|(_, count)| count).sum(),
+            sum_nanos: self.sum_nanos.load(Ordering::Relaxed),
+            buckets,
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for LatencyHistogram {
+    fn clone(&self) -> Self {
+        let clone = Self::new();
+        for (target, source) in clone.buckets.iter().zip(self.buckets.iter()) {
+            target.store(source.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        clone.sum_nanos.store(self.sum_nanos.load(Ordering::Relaxed), Ordering::Relaxed);
+        clone
+    }
+}
+
+impl LatencySnapshot {
+    /// Upper bound of the bucket holding the q-th quantile (0.0..=1.0), None without observations.
+    pub fn quantile(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((q.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        self.buckets
+            .iter()
+            .find(|(_, count)| {
+                seen += count;
+                seen >= rank
+            })
+            .map(|(bound, _)| Duration::from_nanos(*bound))
+    }
+
+    pub fn p50(&self) -> Option<Duration> {
+        self.quantile(0.50)
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.quantile(0.95)
+    }
+
+    pub fn p99(&self) -> Option<Duration> {
+        self.quantile(0.99)
+    }
+
+    /// Mean latency, None without observations.
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0).then(|| Duration::from_nanos(self.sum_nanos / self.count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantiles_report_bucket_upper_bounds() {
+        let histogram = LatencyHistogram::new();
+        for _ in 0..90 {
+            histogram.record(Duration::from_micros(3));
+        }
+        for _ in 0..10 {
+            histogram.record(Duration::from_millis(2));
+        }
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count, 100);
+        assert_eq!(snapshot.p50(), Some(Duration::from_micros(4)));
+        assert_eq!(snapshot.p95(), Some(Duration::from_nanos(BASE_BOUND_NANOS << 11)));
+        assert!(snapshot.p99() >= snapshot.p95());
+        assert_eq!(LatencySnapshot::default().p50(), None);
+    }
+}
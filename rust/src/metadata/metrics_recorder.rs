@@ -0,0 +1,117 @@
+use crate::error::TectonicError;
+use crate::metadata::latency_histogram::{LatencyHistogram, LatencySnapshot};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Atomic activity counters shared by cache operations, a no-op when disabled.
+pub struct MetricsRecorder {
+    /// Whether activity is recorded (Immutable).
+    enabled: bool,
+
+    queries: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    inserts: AtomicU64,
+    duplicates: AtomicU64,
+    evictions: AtomicU64,
+
+    /// Lookup latency distribution (Mutable).
+    query_latency: LatencyHistogram,
+}
+
+/// Activity counters copied out of a recorder.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActivitySnapshot {
+    pub queries: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub inserts: u64,
+    pub duplicates: u64,
+    pub evictions: u64,
+    pub query_latency: LatencySnapshot,
+}
+
+impl MetricsRecorder {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            queries: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            inserts: AtomicU64::new(0),
+            duplicates: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            query_latency: LatencyHistogram::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Start timing an operation, None (and no clock read) when disabled.
+    pub fn start(&self) -> Option<Instant> {
+        self.enabled.then(Instant::now)
+    }
+
+    pub fn record_query(&self, started: Option<Instant>, hit: bool) {
+        let Some(started) = started else {
+            return;
+        };
+
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        self.query_latency.record(started.elapsed());
+    }
+
+    /// Count a successful insert, or a duplicate rejection.
+    pub fn record_insert<T>(&self, result: &Result<T, TectonicError>) {
+        if !self.enabled {
+            return;
+        }
+
+        match result {
+            Ok(_) => self.inserts.fetch_add(1, Ordering::Relaxed),
+            Err(TectonicError::DuplicateEntry { .. }) => self.duplicates.fetch_add(1, Ordering::Relaxed),
+            Err(_) => return,
+        };
+    }
+
+    pub fn record_evictions(&self, count: usize) {
+        if self.enabled && count > 0 {
+            self.evictions.fetch_add(count as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> ActivitySnapshot {
+        ActivitySnapshot {
+            queries: self.queries.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            inserts: self.inserts.load(Ordering::Relaxed),
+            duplicates: self.duplicates.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            query_latency: self.query_latency.snapshot(),
+        }
+    }
+}
+
+impl Clone for MetricsRecorder {
+    fn clone(&self) -> Self {
+        let counter = |value: &AtomicU64| AtomicU64::new(value.load(Ordering::Relaxed));
+        Self {
+            enabled: self.enabled,
+            queries: counter(&self.queries),
+            hits: counter(&self.hits),
+            misses: counter(&self.misses),
+            inserts: counter(&self.inserts),
+            duplicates: counter(&self.duplicates),
+            evictions: counter(&self.evictions),
+            query_latency: self.query_latency.clone(),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::metadata::prometheus_exporter::PROMETHEUS_CONTENT_TYPE;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/* ==============================
+    * Metrics HTTP Endpoint
+    *
+    * Minimal blocking HTTP/1.1 server (std::net only) answering GET /metrics with
+    * the rendered Prometheus exposition. Intended for scrapers, not general traffic:
+    * requests are served one at a time on a single background thread.
+============================== */
+
+/// Upper bound on time spent reading a single request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct MetricsServer {
+    /// Address the listener is bound to (Immutable).
+    local_addr: SocketAddr,
+
+    /// Set to stop accepting connections (Mutable).
+    shutdown: Arc<AtomicBool>,
+
+    /// Background accept loop (Mutable).
+    worker: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Bind the endpoint and serve `render()` on GET /metrics from a background thread.
+    pub fn serve<A, F>(addr: A, render: F) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+        F: Fn() -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let stop = Arc::clone(&shutdown);
+        let worker = thread::Builder::new()
+            .name("tectonic-metrics".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Acquire) {
+                        break;
+                    }
+                    // A failed exchange only affects that scrape.
+                    if let Ok(stream) = stream {
+                        let _ = Self::respond(stream, &render);
+                    }
+                }
+            })?;
+
+        Ok(Self { local_addr, shutdown, worker: Some(worker) })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop the accept loop and wait for the background thread to exit.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.shutdown.store(true, Ordering::Release);
+
+        // Wake the blocking accept() so the loop observes the flag.
+        let _ = TcpStream::connect(self.local_addr);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+
+    fn respond<F: Fn() -> String>(mut stream: TcpStream, render: &F) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+
+        // Drain headers up to the blank line.
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 2 {
+            header.clear();
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let path = path.split('?').next().unwrap_or("");
+
+        let (status, content_type, body) = match (method, path) {
+            ("GET", "/metrics") => ("200 OK", PROMETHEUS_CONTENT_TYPE, render()),
+            ("GET", _) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+            _ => ("405 Method Not Allowed", "text/plain", "method not allowed\n".to_string()),
+        };
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        )?;
+        stream.flush()
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            self.stop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_metrics_and_rejects_other_paths() {
+        let server = MetricsServer::serve("127.0.0.1:0", || "tectonic_queries_total 3\n".to_string()).unwrap();
+
+        let response = get(server.local_addr(), "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("tectonic_queries_total 3\n"));
+        assert!(get(server.local_addr(), "/").starts_with("HTTP/1.1 404"));
+
+        server.shutdown();
+    }
+}
@@ -0,0 +1,6 @@
+pub mod cache_metrics;
+pub mod latency_histogram;
+pub mod metrics_recorder;
+pub mod prometheus_exporter;
+#[cfg(feature = "http-exporter")]
+pub mod metrics_server;
\ No newline at end of file
@@ -0,0 +1,109 @@
+use crate::metadata::cache_metrics::CacheMetrics;
+use std::fmt::Write;
+
+/// Prefix shared by every exported metric name.
+const NAMESPACE: &str = "tectonic";
+
+/// Content type of the Prometheus text exposition format.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Render a metrics snapshot in the Prometheus text exposition format (version 0.0.4).
+pub fn encode_metrics(metrics: &CacheMetrics) -> String {
+    let mut out = String::new();
+    let cache = format!("cache_id=\"{}\"", escape_label(&metrics.cache_id));
+
+    let counters = [
+        ("queries_total", "Lookups served by the cache.", metrics.queries),
+        ("hits_total", "Lookups returning an entry within the threshold.", metrics.hits),
+        ("misses_total", "Lookups returning nothing within the threshold.", metrics.misses),
+        ("inserts_total", "Vectors stored or overwritten.", metrics.inserts),
+        ("duplicates_total", "Inserts rejected as duplicates.", metrics.duplicates),
+        ("evictions_total", "Entries removed by the eviction policy.", metrics.evictions),
+    ];
+    for (name, help, value) in counters {
+        write_family(&mut out, name, "counter", help);
+        let _ = writeln!(out, "{}_{}{{{}}} {}", NAMESPACE, name, cache, value);
+    }
+
+    write_family(&mut out, "cache_entries", "gauge", "Number of stored entries.");
+    let _ = writeln!(out, "{}_cache_entries{{{}}} {}", NAMESPACE, cache, metrics.size);
+    write_family(&mut out, "cache_capacity", "gauge", "Maximum number of stored entries.");
+    let _ = writeln!(out, "{}_cache_capacity{{{}}} {}", NAMESPACE, cache, metrics.capacity);
+    write_family(&mut out, "cache_fill_ratio", "gauge", "Ratio of stored entries to capacity.");
+    let _ = writeln!(out, "{}_cache_fill_ratio{{{}}} {}", NAMESPACE, cache, metrics.fill_ratio());
+
+    write_family(&mut out, "partition_entries", "gauge", "Number of entries stored per partition.");
+    for partition in &metrics.partitions {
+        let _ = writeln!(out, "{}_partition_entries{{{},partition=\"{}\"}} {}", NAMESPACE, cache, partition.partition_id, partition.entries);
+    }
+    write_family(&mut out, "partition_capacity", "gauge", "Maximum number of entries per partition.");
+    for partition in &metrics.partitions {
+        let _ = writeln!(out, "{}_partition_capacity{{{},partition=\"{}\"}} {}", NAMESPACE, cache, partition.partition_id, partition.capacity);
+    }
+
+    // Histogram buckets are cumulative, ending with the +Inf bucket.
+    write_family(&mut out, "query_latency_seconds", "histogram", "Lookup latency in seconds.");
+    let latency = &metrics.query_latency;
+    let mut cumulative = 0;
+    for (bound, count) in &latency.buckets {
+        cumulative += count;
+        let le = if *bound == u64::MAX { "+Inf".to_string() } else { format_seconds(*bound) };
+        let _ = writeln!(out, "{}_query_latency_seconds_bucket{{{},le=\"{}\"}} {}", NAMESPACE, cache, le, cumulative);
+    }
+    if latency.buckets.is_empty() {
+        let _ = writeln!(out, "{}_query_latency_seconds_bucket{{{},le=\"+Inf\"}} 0", NAMESPACE, cache);
+    }
+    let _ = writeln!(out, "{}_query_latency_seconds_sum{{{}}} {}", NAMESPACE, cache, format_seconds(latency.sum_nanos));
+    let _ = writeln!(out, "{}_query_latency_seconds_count{{{}}} {}", NAMESPACE, cache, latency.count);
+
+    out
+}
+
+impl CacheMetrics {
+    /// Render the snapshot in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        encode_metrics(self)
+    }
+}
+
+fn write_family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {}_{} {}", NAMESPACE, name, help);
+    let _ = writeln!(out, "# TYPE {}_{} {}", NAMESPACE, name, kind);
+}
+
+fn format_seconds(nanos: u64) -> String {
+    format!("{}", nanos as f64 / 1e9)
+}
+
+/// Escape a label value (backslash, double quote and line feed).
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::vector_cache::VectorCache;
+
+    #[test]
+    fn renders_labelled_counters_gauges_and_histograms() {
+        let mut cache: VectorCache<3> = VectorCache::builder()
+            .cache_id("prompts \"v2\"")
+            .max_entries(8)
+            .partition_count(2)
+            .metric("euclidean")
+            .build()
+            .unwrap();
+        cache.insert(&[1.0, 0.0, 0.0], (), false).unwrap();
+        cache.query(&[1.0, 0.0, 0.0], 1, 0.5).unwrap();
+
+        let text = encode_metrics(&cache.metrics());
+        let label = "cache_id=\"prompts \\\"v2\\\"\"";
+        assert!(text.contains("# TYPE tectonic_queries_total counter"));
+        assert!(text.contains(&format!("tectonic_hits_total{{{}}} 1", label)));
+        assert!(text.contains(&format!("tectonic_cache_entries{{{}}} 1", label)));
+        assert!(text.contains(&format!("tectonic_partition_capacity{{{},partition=\"1\"}} 4", label)));
+        assert!(text.contains(&format!("tectonic_query_latency_seconds_bucket{{{},le=\"+Inf\"}} 1", label)));
+        assert!(text.contains(&format!("tectonic_query_latency_seconds_count{{{}}} 1", label)));
+    }
+}
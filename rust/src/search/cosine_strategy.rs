@@ -1,4 +1,5 @@
 use crate::search::distance_metric::DistanceMetric;
+use crate::utility::product_quantizer::AdcDecomposition;
 
 #[derive(Clone)]
 pub struct CosineProduct;
@@ -22,4 +23,27 @@ impl<const D: usize> DistanceMetric<D> for CosineProduct {
 
         1.0 - (dot_product / (norm_x.sqrt() * norm_y.sqrt()))
     }
+
+    fn distance_int8(&self, x: &[f32; D], codes: &[u8; D], min: f32, scale: f32) -> f32 {
+        let mut dot_product = 0.0;
+        let mut norm_x = 0.0;
+        let mut norm_y = 0.0;
+
+        for i in 0..D {
+            let y = min + codes[i] as f32 * scale;
+            dot_product += x[i] * y;
+            norm_x += x[i] * x[i];
+            norm_y += y * y;
+        }
+
+        if norm_x == 0.0 || norm_y == 0.0 {
+            return 1.0;
+        }
+
+        1.0 - (dot_product / (norm_x.sqrt() * norm_y.sqrt()))
+    }
+
+    fn adc_decomposition(&self) -> Option<AdcDecomposition> {
+        Some(AdcDecomposition::Cosine)
+    }
 }
\ No newline at end of file
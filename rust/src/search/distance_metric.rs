@@ -1,9 +1,30 @@
+use crate::utility::product_quantizer::AdcDecomposition;
+use crate::utility::vector_utils::scalar_decode;
+use crate::vector::stored_vector::HalfFormat;
+
 
 
 pub trait DistanceMetric<const D: usize>: Send + Sync {
     /// Compute distance between two vectors of dimension D.
     /// Lower distance indicates higher similarity.
     fn distance(&self, x: &[f32; D], y: &[f32; D]) -> f32;
+
+    /// Distance to an int8 vector stored as `min + code * scale`.
+    /// Built-in metrics fold the dequantization into their accumulation loop.
+    fn distance_int8(&self, x: &[f32; D], codes: &[u8; D], min: f32, scale: f32) -> f32 {
+        self.distance(x, &scalar_decode(codes, min, scale))
+    }
+
+    /// Distance to a half-precision vector, widened to f32 before accumulating.
+    fn distance_half(&self, x: &[f32; D], bits: &[u16; D], format: HalfFormat) -> f32 {
+        self.distance(x, &bits.map(|value| format.decode(value)))
+    }
+
+    /// Subspace decomposition used to score product-quantized vectors from lookup tables.
+    /// Metrics without one are scored against decoded vectors.
+    fn adc_decomposition(&self) -> Option<AdcDecomposition> {
+        None
+    }
 }
 
 pub trait DistanceMetricDyn<const D: usize>: DistanceMetric<D> {
@@ -1,4 +1,5 @@
 use crate::search::distance_metric::DistanceMetric;
+use crate::utility::product_quantizer::AdcDecomposition;
 
 #[derive(Clone)]
 pub struct DotProduct;
@@ -11,5 +12,20 @@ impl<const D: usize> DistanceMetric<D> for DotProduct {
             similarity += x[i] * y[i];
         }
         -similarity
-    }   
+    }
+
+    fn distance_int8(&self, x: &[f32; D], codes: &[u8; D], min: f32, scale: f32) -> f32 {
+        // x . (min + c * scale) = min * sum(x) + scale * (x . c)
+        let mut sum = 0.0;
+        let mut weighted = 0.0;
+        for i in 0..D {
+            sum += x[i];
+            weighted += x[i] * codes[i] as f32;
+        }
+        -(min * sum + scale * weighted)
+    }
+
+    fn adc_decomposition(&self) -> Option<AdcDecomposition> {
+        Some(AdcDecomposition::NegativeDot)
+    }
 }
\ No newline at end of file
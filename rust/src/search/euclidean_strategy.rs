@@ -1,4 +1,5 @@
 use crate::search::distance_metric::DistanceMetric;
+use crate::utility::product_quantizer::AdcDecomposition;
 
 #[derive(Clone)]
 pub struct EuclideanProduct;
@@ -12,4 +13,17 @@ impl<const D: usize> DistanceMetric<D> for EuclideanProduct {
         }
         result
     }
+
+    fn distance_int8(&self, x: &[f32; D], codes: &[u8; D], min: f32, scale: f32) -> f32 {
+        let mut result = 0.0;
+        for i in 0..D {
+            let distance = x[i] - (min + codes[i] as f32 * scale);
+            result += distance * distance;
+        }
+        result
+    }
+
+    fn adc_decomposition(&self) -> Option<AdcDecomposition> {
+        Some(AdcDecomposition::SquaredEuclidean)
+    }
 }
\ No newline at end of file
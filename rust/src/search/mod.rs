@@ -1,4 +1,9 @@
 pub mod distance_metric;
 pub mod cosine_strategy;
 pub mod dot_strategy;
-pub mod euclidean_strategy;
\ No newline at end of file
+pub mod euclidean_strategy;
+pub mod probe_mode;
+pub mod search_result;
+pub mod top_k_heap;
+pub mod rerank;
+pub mod query_mode;
\ No newline at end of file
@@ -0,0 +1,41 @@
+
+/// Strategy for selecting which partitions are scanned during a query.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProbeMode {
+    /// Scan only the N partitions whose centroids are closest to the query.
+    Nearest(usize),
+
+    /// Scan every partition regardless of centroid distance.
+    /// Centroids drift between rebuilds, so only exhaustive probing never misses a stored vector.
+    #[default]
+    Exhaustive,
+}
+
+impl ProbeMode {
+    /// Number of partitions to probe out of the available `partition_count`.
+    pub fn probe_count(&self, partition_count: usize) -> usize {
+        match self {
+            ProbeMode::Nearest(nprobe) => (*nprobe).min(partition_count),
+            ProbeMode::Exhaustive => partition_count,
+        }
+    }
+
+    /// Pick partitions from `(index, spilled)` candidates ranked by centroid distance.
+    /// Spilled candidates are probed without using up the budget: they redirected inserts
+    /// to a farther partition when full, or have an unset centroid that cannot be ranked.
+    /// Partitions that are merely full still count against the budget.
+    pub fn select(&self, ranked: impl IntoIterator<Item = (usize, bool)>) -> Vec<usize> {
+        let mut remaining = self.probe_count(usize::MAX);
+        let mut selected = Vec::new();
+        for (idx, spilled) in ranked {
+            if remaining == 0 {
+                break;
+            }
+            selected.push(idx);
+            if !spilled {
+                remaining -= 1;
+            }
+        }
+        selected
+    }
+}
@@ -0,0 +1,25 @@
+/// Strategy for scoring candidates within the probed partitions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueryMode {
+    /// Score every candidate with the configured distance metric.
+    #[default]
+    Direct,
+
+    /// Rank candidates by Hamming distance between packed sign codes, then
+    /// re-score the closest `oversampling * top_k` with the distance metric.
+    Binary { oversampling: usize },
+}
+
+impl QueryMode {
+    /// Number of candidates kept by the first pass for a query returning `top_k` results.
+    pub fn candidate_pool(&self, top_k: usize) -> usize {
+        match self {
+            QueryMode::Direct => top_k,
+            QueryMode::Binary { oversampling } => top_k.saturating_mul((*oversampling).max(1)),
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        matches!(self, QueryMode::Binary { .. })
+    }
+}
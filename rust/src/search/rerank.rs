@@ -0,0 +1,21 @@
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::vector::vector_entry::VectorEntry;
+
+/// Re-score approximate candidates against full-precision vectors, keeping the closest top_k within the threshold.
+pub fn rerank<'a, const D: usize, V>(
+    candidates: Vec<(f32, &'a VectorEntry<D, V>)>,
+    vector: &[f32; D],
+    metric: &dyn DistanceMetricDyn<D>,
+    threshold: f32,
+    top_k: usize,
+) -> Vec<(f32, &'a VectorEntry<D, V>)> {
+    let mut rescored: Vec<(f32, &'a VectorEntry<D, V>)> = candidates
+        .into_iter()
+        .map(|(_, entry)| (entry.vector.exact_distance(vector, metric), entry))
+        .filter(|(score, _)| *score <= threshold)
+        .collect();
+
+    rescored.sort_by(|a, b| a.0.total_cmp(&b.0));
+    rescored.truncate(top_k);
+    rescored
+}
@@ -0,0 +1,16 @@
+use crate::vector::vector_entry::VectorEntry;
+
+#[derive(Clone)]
+pub struct SearchResult<const D: usize, V = ()> {
+    /// Matched vector entry and its payload (Immutable).
+    pub entry: VectorEntry<D, V>,
+
+    /// Distance between the query and the entry, lower is more similar (Immutable).
+    pub score: f32,
+}
+
+impl<const D: usize, V> SearchResult<D, V> {
+    pub fn new(entry: VectorEntry<D, V>, score: f32) -> Self {
+        Self { entry, score }
+    }
+}
@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Heap element ordered by distance, worst (largest) distance on top.
+struct HeapItem<T> {
+    distance: f32,
+    item: T,
+}
+
+impl<T> PartialEq for HeapItem<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance.total_cmp(&other.distance) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for HeapItem<T> {}
+
+impl<T> PartialOrd for HeapItem<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for HeapItem<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+/// Bounded max-heap retaining the `capacity` closest items seen so far.
+pub struct TopKHeap<T> {
+    /// Maximum number of items retained (Immutable).
+    capacity: usize,
+
+    /// Internal max-heap keyed on distance (Mutable).
+    heap: BinaryHeap<HeapItem<T>>,
+}
+
+impl<T> TopKHeap<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity.saturating_add(1)),
+        }
+    }
+
+    /// Offer an item to the heap, returns true if it was retained.
+    pub fn push(&mut self, distance: f32, item: T) -> bool {
+        if self.capacity == 0 || distance.is_nan() {
+            return false;
+        }
+
+        if self.heap.len() < self.capacity {
+            self.heap.push(HeapItem { distance, item });
+            return true;
+        }
+
+        // Heap is full -> only replace the current worst candidate.
+        match self.heap.peek() {
+            Some(worst) if distance < worst.distance => {
+                self.heap.pop();
+                self.heap.push(HeapItem { distance, item });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Maximum number of items retained.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Distance of the worst retained item, if any.
+    pub fn worst_distance(&self) -> Option<f32> {
+        self.heap.peek().map(|worst| worst.distance)
+    }
+
+    /// Move every item of another heap into this one.
+    pub fn merge(&mut self, other: TopKHeap<T>) {
+        for entry in other.heap {
+            self.push(entry.distance, entry.item);
+        }
+    }
+
+    /// Consume the heap, returning items ordered from closest to furthest.
+    pub fn into_sorted_vec(self) -> Vec<(f32, T)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|entry| (entry.distance, entry.item))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.heap.len() >= self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retains_closest_items_in_order() {
+        let mut heap = TopKHeap::new(3);
+        for (distance, id) in [(5.0, 5), (1.0, 1), (4.0, 4), (2.0, 2), (3.0, 3)] {
+            heap.push(distance, id);
+        }
+
+        let ids: Vec<u32> = heap.into_sorted_vec().into_iter().map(|(_, id)| id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn merge_respects_capacity() {
+        let mut left = TopKHeap::new(2);
+        left.push(3.0, "c");
+        left.push(1.0, "a");
+
+        let mut right = TopKHeap::new(2);
+        right.push(2.0, "b");
+        right.push(4.0, "d");
+
+        left.merge(right);
+        assert_eq!(left.len(), 2);
+        assert_eq!(left.worst_distance(), Some(2.0));
+    }
+}
@@ -7,12 +7,22 @@ pub fn hash_u64(value: u64) -> u64 {
     hasher.finish()
 }
 
-pub fn hash_vector_id<const D: usize>(vector: &[u8; D]) -> u64 {
+/// Hash of the exact component bits, so only bit-identical vectors collide by design.
+pub fn hash_vector_bits<const D: usize>(vector: &[f32; D]) -> u64 {
     let mut hasher = DefaultHasher::new();
-    vector.hash(&mut hasher);
+    vector.map(f32::to_bits).hash(&mut hasher);
     hasher.finish()
 }
 
-pub fn generate_vector_id<const D: usize>(vector: &[u8; D]) -> u64 {
-        hash_vector_id(vector)
+/// Jump consistent hash (Lamping & Veach) mapping a key onto one of `buckets`.
+/// Growing from n to n + 1 buckets only moves ~1/(n + 1) of the keys.
+pub fn jump_consistent_hash(mut key: u64, buckets: usize) -> usize {
+    let mut bucket: i64 = -1;
+    let mut next: i64 = 0;
+    while next < buckets as i64 {
+        bucket = next;
+        key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+        next = ((bucket + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    bucket.max(0) as usize
 }
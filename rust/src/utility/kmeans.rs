@@ -0,0 +1,185 @@
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::utility::random_util::XorShiftRng;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KMeansConfig {
+    /// Maximum number of Lloyd iterations per run.
+    pub max_iterations: usize,
+
+    /// Convergence threshold on the largest squared centroid shift.
+    pub tolerance: f32,
+}
+
+impl Default for KMeansConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 25,
+            tolerance: 1e-4,
+        }
+    }
+}
+
+pub struct KMeansResult<C> {
+    /// Final cluster centroids (at most k, fewer when there are fewer vectors).
+    pub centroids: Vec<C>,
+
+    /// Index of the assigned centroid for every input vector.
+    pub assignments: Vec<usize>,
+
+    /// Number of Lloyd iterations performed.
+    pub iterations: usize,
+}
+
+pub fn squared_euclidean<const D: usize>(x: &[f32; D], y: &[f32; D]) -> f32 {
+    squared_distance(x, y)
+}
+
+/// Squared Euclidean distance between equally sized slices.
+pub fn squared_distance(x: &[f32], y: &[f32]) -> f32 {
+    x.iter().zip(y.iter()).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+pub fn nearest_centroid<C>(vector: &C, centroids: &[C], distance: &impl Fn(&C, &C) -> f32) -> usize {
+    let mut best = (f32::INFINITY, 0);
+    for (idx, centroid) in centroids.iter().enumerate() {
+        let distance = distance(vector, centroid);
+        if distance < best.0 {
+            best = (distance, idx);
+        }
+    }
+    best.1
+}
+
+/// Choose up to k initial centroids with k-means++ (D^2 weighted) seeding.
+pub fn kmeans_plus_plus<C: Clone + AsRef<[f32]>>(vectors: &[&C], k: usize, rng: &mut XorShiftRng) -> Vec<C> {
+    let k = k.min(vectors.len());
+    let mut centroids: Vec<C> = Vec::with_capacity(k);
+    if k == 0 {
+        return centroids;
+    }
+
+    centroids.push(vectors[rng.next_index(vectors.len())].clone());
+    let mut weights: Vec<f32> = vectors
+        .iter()
+        .map(|v| squared_distance(v.as_ref(), centroids[0].as_ref()))
+        .collect();
+
+    while centroids.len() < k {
+        let total: f32 = weights.iter().sum();
+
+        // Every vector coincides with a centroid -> fall back to uniform choice.
+        let chosen = if total <= 0.0 {
+            rng.next_index(vectors.len())
+        } else {
+            let mut target = rng.next_f32() * total;
+            let mut chosen = vectors.len() - 1;
+            for (idx, weight) in weights.iter().enumerate() {
+                if target < *weight {
+                    chosen = idx;
+                    break;
+                }
+                target -= weight;
+            }
+            chosen
+        };
+
+        let centroid = vectors[chosen].clone();
+        for (weight, vector) in weights.iter_mut().zip(vectors.iter()) {
+            *weight = weight.min(squared_distance(vector.as_ref(), centroid.as_ref()));
+        }
+        centroids.push(centroid);
+    }
+
+    centroids
+}
+
+/// Cluster vectors into at most k groups, assigning by the configured metric.
+pub fn kmeans<const D: usize>(
+    vectors: &[&[f32; D]],
+    k: usize,
+    config: &KMeansConfig,
+    metric: &dyn DistanceMetricDyn<D>,
+    rng: &mut XorShiftRng,
+) -> KMeansResult<[f32; D]> {
+    kmeans_by(vectors, k, config, |x, y| metric.distance(x, y), rng)
+}
+
+/// Cluster fixed-size arrays or runtime-length sub-vectors (e.g. product-quantization subspaces),
+/// assigning by the given distance and averaging components for the update step.
+pub fn kmeans_by<C, F>(vectors: &[&C], k: usize, config: &KMeansConfig, distance: F, rng: &mut XorShiftRng) -> KMeansResult<C>
+where
+    C: Clone + AsRef<[f32]> + AsMut<[f32]>,
+    F: Fn(&C, &C) -> f32,
+{
+    let mut centroids = kmeans_plus_plus(vectors, k, rng);
+    let mut assignments = vec![0; vectors.len()];
+    let mut iterations = 0;
+
+    // Base Case -> Nothing to cluster.
+    if centroids.is_empty() {
+        return KMeansResult { centroids, assignments, iterations };
+    }
+
+    while iterations < config.max_iterations {
+        iterations += 1;
+
+        // Assignment step.
+        let mut changed = false;
+        for (assignment, vector) in assignments.iter_mut().zip(vectors.iter()) {
+            let nearest = nearest_centroid(*vector, &centroids, &distance);
+            changed |= *assignment != nearest;
+            *assignment = nearest;
+        }
+
+        // Update step -> empty clusters keep their previous centroid.
+        let mut sums: Vec<C> = centroids.clone();
+        sums.iter_mut().for_each(|sum| sum.as_mut().fill(0.0));
+        let mut counts = vec![0usize; centroids.len()];
+        for (assignment, vector) in assignments.iter().zip(vectors.iter()) {
+            counts[*assignment] += 1;
+            for (sum, value) in sums[*assignment].as_mut().iter_mut().zip(vector.as_ref()) {
+                *sum += *value;
+            }
+        }
+
+        let mut max_shift = 0.0f32;
+        for ((centroid, mut updated), count) in centroids.iter_mut().zip(sums).zip(counts) {
+            if count == 0 {
+                continue;
+            }
+            updated.as_mut().iter_mut().for_each(|x| *x /= count as f32);
+            max_shift = max_shift.max(squared_distance(centroid.as_ref(), updated.as_ref()));
+            *centroid = updated;
+        }
+
+        if (!changed && iterations > 1) || max_shift <= config.tolerance {
+            break;
+        }
+    }
+
+    // Final assignment against the converged centroids.
+    for (assignment, vector) in assignments.iter_mut().zip(vectors.iter()) {
+        *assignment = nearest_centroid(*vector, &centroids, &distance);
+    }
+
+    KMeansResult { centroids, assignments, iterations }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::euclidean_strategy::EuclideanProduct;
+
+    #[test]
+    fn separates_well_spaced_clusters() {
+        let points = [[0.0, 0.0], [0.1, 0.0], [0.0, 0.1], [10.0, 10.0], [10.1, 10.0], [10.0, 10.1]];
+        let vectors: Vec<&[f32; 2]> = points.iter().collect();
+        let mut rng = XorShiftRng::new(7);
+
+        let result = kmeans(&vectors, 2, &KMeansConfig::default(), &EuclideanProduct, &mut rng);
+        assert_eq!(result.centroids.len(), 2);
+        assert_eq!(result.assignments[0], result.assignments[2]);
+        assert_eq!(result.assignments[3], result.assignments[5]);
+        assert_ne!(result.assignments[0], result.assignments[3]);
+    }
+}
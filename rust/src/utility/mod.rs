@@ -1,2 +1,7 @@
 pub mod hashing_util;
+pub mod kmeans;
+pub mod product_quantizer;
+pub mod random_util;
+pub mod scalar_quantizer;
+pub mod time_util;
 pub mod vector_utils;
\ No newline at end of file
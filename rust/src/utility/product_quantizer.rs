@@ -0,0 +1,245 @@
+use crate::error::TectonicError;
+use crate::utility::kmeans::{kmeans_by, squared_distance, KMeansConfig};
+use crate::utility::random_util::XorShiftRng;
+
+/* ==============================
+    * Product Quantizer
+    *
+    * Splits D-dimensional vectors into `subspaces` contiguous sub-vectors and
+    * replaces each with the index of its nearest codebook centroid, one byte per
+    * subspace. Queries are scored with asymmetric distance computation (ADC):
+    * the query stays in f32, its partial distances to every centroid are
+    * tabulated once, and each stored vector costs `subspaces` table lookups.
+============================== */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProductQuantizerConfig {
+    /// Number of contiguous sub-vectors, must divide the vector dimension.
+    pub subspaces: usize,
+
+    /// Codebook size per subspace, at most 256 so codes fit in a byte.
+    pub centroids: usize,
+}
+
+impl Default for ProductQuantizerConfig {
+    fn default() -> Self {
+        Self {
+            subspaces: 8,
+            centroids: 256,
+        }
+    }
+}
+
+impl ProductQuantizerConfig {
+    pub fn validate(&self, dimension: usize) -> Result<(), TectonicError> {
+        if self.subspaces == 0 || !dimension.is_multiple_of(self.subspaces) {
+            return Err(TectonicError::InvalidConfig(format!(
+                "pq subspaces must divide the vector dimension {}, got {}",
+                dimension, self.subspaces
+            )));
+        }
+        if !(1..=256).contains(&self.centroids) {
+            return Err(TectonicError::InvalidConfig(format!(
+                "pq centroids must be within [1, 256], got {}",
+                self.centroids
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How a metric's distance decomposes over subspaces for lookup-table scoring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdcDecomposition {
+    /// Sum of per-subspace squared distances.
+    SquaredEuclidean,
+
+    /// Negated sum of per-subspace dot products.
+    NegativeDot,
+
+    /// Per-subspace dot products and squared norms, combined after the lookups.
+    Cosine,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProductQuantizer<const D: usize> {
+    /// Number of contiguous sub-vectors (Immutable).
+    subspaces: usize,
+
+    /// Trained centroids per subspace (Immutable).
+    centroids: usize,
+
+    /// Flattened codebooks indexed as [subspace][centroid][component] (Immutable).
+    codebooks: Vec<f32>,
+
+    /// Squared norm of every codebook centroid, indexed as [subspace][centroid] (Immutable).
+    norms: Vec<f32>,
+}
+
+/// Per-query partial distances against one quantizer's codebooks.
+pub struct AdcTable<'a, const D: usize> {
+    quantizer: &'a ProductQuantizer<D>,
+    decomposition: AdcDecomposition,
+    partials: Vec<f32>,
+    query_norm: f32,
+}
+
+impl<const D: usize> ProductQuantizer<D> {
+    /// Learn one codebook per subspace with k-means over the training vectors.
+    pub fn train(
+        vectors: &[&[f32; D]],
+        config: ProductQuantizerConfig,
+        kmeans_config: &KMeansConfig,
+        rng: &mut XorShiftRng,
+    ) -> Result<Self, TectonicError> {
+        config.validate(D)?;
+
+        // Base Case -> A codebook needs at least one training vector.
+        if vectors.is_empty() {
+            return Err(TectonicError::InvalidConfig("pq training requires at least one vector".to_string()));
+        }
+
+        let sub_dim = D / config.subspaces;
+        let centroids = config.centroids.min(vectors.len());
+        let mut codebooks = Vec::with_capacity(config.subspaces * centroids * sub_dim);
+        for subspace in 0..config.subspaces {
+            let range = subspace * sub_dim..(subspace + 1) * sub_dim;
+            let points: Vec<Vec<f32>> = vectors.iter().map(|vector| vector[range.clone()].to_vec()).collect();
+            let points: Vec<&Vec<f32>> = points.iter().collect();
+            let clustering = kmeans_by(&points, centroids, kmeans_config, |x, y| squared_distance(x, y), rng);
+            codebooks.extend(clustering.centroids.concat());
+        }
+
+        let norms = codebooks.chunks(sub_dim).map(|centroid| centroid.iter().map(|x| x * x).sum()).collect();
+        Ok(Self { subspaces: config.subspaces, centroids, codebooks, norms })
+    }
+
+    fn sub_dim(&self) -> usize {
+        D / self.subspaces
+    }
+
+    fn centroid(&self, subspace: usize, code: usize) -> &[f32] {
+        let start = (subspace * self.centroids + code) * self.sub_dim();
+        &self.codebooks[start..start + self.sub_dim()]
+    }
+
+    pub fn encode(&self, vector: &[f32; D]) -> Box<[u8]> {
+        vector
+            .chunks(self.sub_dim())
+            .enumerate()
+            .map(|(subspace, sub_vector)| {
+                (0..self.centroids)
+                    .map(|code| (squared_distance(sub_vector, self.centroid(subspace, code)), code))
+                    .min_by(|a, b| a.0.total_cmp(&b.0))
+                    .map_or(0, |(_, code)| code as u8)
+            })
+            .collect()
+    }
+
+    pub fn decode(&self, codes: &[u8]) -> [f32; D] {
+        let mut vector = [0.0f32; D];
+        for (subspace, (chunk, code)) in vector.chunks_mut(self.sub_dim()).zip(codes).enumerate() {
+            chunk.copy_from_slice(self.centroid(subspace, *code as usize));
+        }
+        vector
+    }
+
+    /// Tabulate the query's partial distances to every centroid.
+    pub fn lookup_table(&self, query: &[f32; D], decomposition: AdcDecomposition) -> AdcTable<'_, D> {
+        let mut partials = Vec::with_capacity(self.subspaces * self.centroids);
+        for (subspace, sub_query) in query.chunks(self.sub_dim()).enumerate() {
+            for code in 0..self.centroids {
+                let centroid = self.centroid(subspace, code);
+                partials.push(match decomposition {
+                    AdcDecomposition::SquaredEuclidean => squared_distance(sub_query, centroid),
+                    AdcDecomposition::NegativeDot => -dot(sub_query, centroid),
+                    AdcDecomposition::Cosine => dot(sub_query, centroid),
+                });
+            }
+        }
+
+        AdcTable {
+            quantizer: self,
+            decomposition,
+            partials,
+            query_norm: dot(query, query).sqrt(),
+        }
+    }
+
+    /// Bytes per encoded vector.
+    pub fn code_size(&self) -> usize {
+        self.subspaces
+    }
+
+    /// Bytes held by the codebooks.
+    pub fn size_bytes(&self) -> usize {
+        (self.codebooks.len() + self.norms.len()) * size_of::<f32>()
+    }
+}
+
+impl<const D: usize> AdcTable<'_, D> {
+    /// Whether codes produced by the given quantizer can be scored with this table.
+    pub fn scores(&self, quantizer: &ProductQuantizer<D>) -> bool {
+        std::ptr::eq(self.quantizer, quantizer)
+    }
+
+    pub fn distance(&self, codes: &[u8]) -> f32 {
+        let centroids = self.quantizer.centroids;
+        let lookup = |table: &[f32]| -> f32 {
+            codes.iter().enumerate().map(|(subspace, code)| table[subspace * centroids + *code as usize]).sum()
+        };
+
+        match self.decomposition {
+            AdcDecomposition::SquaredEuclidean | AdcDecomposition::NegativeDot => lookup(&self.partials),
+            AdcDecomposition::Cosine => {
+                let norm = lookup(&self.quantizer.norms).sqrt();
+                if self.query_norm == 0.0 || norm == 0.0 {
+                    return 1.0;
+                }
+                1.0 - lookup(&self.partials) / (self.query_norm * norm)
+            }
+        }
+    }
+}
+
+fn dot(x: &[f32], y: &[f32]) -> f32 {
+    x.iter().zip(y).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::cosine_strategy::CosineProduct;
+    use crate::search::distance_metric::DistanceMetric;
+    use crate::search::dot_strategy::DotProduct;
+    use crate::search::euclidean_strategy::EuclideanProduct;
+
+    #[test]
+    fn lookup_tables_match_distances_to_decoded_vectors() {
+        let points: Vec<[f32; 8]> = (0..40)
+            .map(|i| std::array::from_fn(|d| ((i * 5 + d * 3) % 11) as f32 - 5.0))
+            .collect();
+        let vectors: Vec<&[f32; 8]> = points.iter().collect();
+        let config = ProductQuantizerConfig { subspaces: 4, centroids: 16 };
+        let quantizer = ProductQuantizer::train(&vectors, config, &KMeansConfig::default(), &mut XorShiftRng::new(3)).unwrap();
+
+        let query = [0.5, -1.0, 2.0, 0.0, 1.5, -2.5, 3.0, 1.0];
+        let codes = quantizer.encode(&points[7]);
+        let decoded = quantizer.decode(&codes);
+        assert_eq!(codes.len(), quantizer.code_size());
+
+        let cases: [(AdcDecomposition, f32); 3] = [
+            (AdcDecomposition::SquaredEuclidean, EuclideanProduct.distance(&query, &decoded)),
+            (AdcDecomposition::NegativeDot, DotProduct.distance(&query, &decoded)),
+            (AdcDecomposition::Cosine, CosineProduct.distance(&query, &decoded)),
+        ];
+        for (decomposition, expected) in cases {
+            let table = quantizer.lookup_table(&query, decomposition);
+            assert!(table.scores(&quantizer));
+            assert!((table.distance(&codes) - expected).abs() < 1e-4);
+        }
+
+        assert!(ProductQuantizerConfig { subspaces: 3, centroids: 16 }.validate(8).is_err());
+        assert!(ProductQuantizerConfig { subspaces: 4, centroids: 257 }.validate(8).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::error::TectonicError;
+
+/* ==============================
+    * Scalar Quantizer
+    *
+    * Maps every component onto 2^bits evenly spaced levels between a calibrated
+    * minimum and maximum. Ranges are learned from data, either independently per
+    * dimension or shared across all dimensions (one range per partition), so
+    * every vector encoded by the quantizer shares the same grid. Degenerate
+    * ranges (constant or non-finite data) collapse onto a single level instead
+    * of dividing by zero, and non-finite components saturate to the end levels.
+============================== */
+
+/// How calibration ranges are shared between dimensions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CalibrationScope {
+    /// Independent range for every dimension.
+    #[default]
+    PerDimension,
+
+    /// One range covering all dimensions, e.g. for a whole partition.
+    Shared,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarQuantizer<const D: usize> {
+    /// Bits per code, 4 or 8 (Immutable).
+    bits: u32,
+
+    /// Value represented by code 0 in each dimension (Immutable).
+    mins: [f32; D],
+
+    /// Distance between adjacent levels in each dimension, 0 for degenerate ranges (Immutable).
+    scales: [f32; D],
+}
+
+impl<const D: usize> ScalarQuantizer<D> {
+    pub fn new(bits: u32, mins: [f32; D], maxs: [f32; D]) -> Result<Self, TectonicError> {
+        if !matches!(bits, 4 | 8) {
+            return Err(TectonicError::InvalidConfig(format!("scalar quantizer bits must be 4 or 8, got {}", bits)));
+        }
+
+        let levels = 1 << bits;
+        let mut scales = [0.0f32; D];
+        for i in 0..D {
+            scales[i] = level_scale(mins[i], maxs[i], levels);
+        }
+        let mins = mins.map(|min| if min.is_finite() { min } else { 0.0 });
+        Ok(Self { bits, mins, scales })
+    }
+
+    /// Learn ranges from the given vectors, ignoring non-finite components.
+    pub fn calibrate(vectors: &[&[f32; D]], bits: u32, scope: CalibrationScope) -> Result<Self, TectonicError> {
+        // Base Case -> Ranges cannot be learned without data.
+        if vectors.is_empty() {
+            return Err(TectonicError::InvalidConfig("scalar quantizer calibration requires at least one vector".to_string()));
+        }
+
+        let mut mins = [f32::INFINITY; D];
+        let mut maxs = [f32::NEG_INFINITY; D];
+        for vector in vectors {
+            for (i, value) in vector.iter().enumerate().filter(|(_, value)| value.is_finite()) {
+                mins[i] = mins[i].min(*value);
+                maxs[i] = maxs[i].max(*value);
+            }
+        }
+
+        if scope == CalibrationScope::Shared {
+            let min = mins.iter().cloned().fold(f32::INFINITY, f32::min);
+            let max = maxs.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            mins = [min; D];
+            maxs = [max; D];
+        }
+
+        Self::new(bits, mins, maxs)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn levels(&self) -> u32 {
+        1 << self.bits
+    }
+
+    /// Value of code 0 and level spacing of the first dimension, shared by all under `CalibrationScope::Shared`.
+    pub fn shared_range(&self) -> (f32, f32) {
+        (self.mins.first().copied().unwrap_or(0.0), self.scales.first().copied().unwrap_or(0.0))
+    }
+
+    /// Whether every finite component lies within half a level of the calibrated range,
+    /// so quantizing the vector never saturates.
+    pub fn covers(&self, vector: &[f32; D]) -> bool {
+        let max_level = (self.levels() - 1) as f32;
+        vector.iter().zip(self.mins.iter().zip(&self.scales)).all(|(value, (min, scale))| {
+            let half_level = scale / 2.0;
+            !value.is_finite() || (*value >= min - half_level && *value <= min + scale * max_level + half_level)
+        })
+    }
+
+    /// One level index per component, each below `levels()`.
+    pub fn quantize(&self, vector: &[f32; D]) -> [u8; D] {
+        let max_level = self.levels() - 1;
+        let mut codes = [0u8; D];
+        for i in 0..D {
+            codes[i] = quantize_level(vector[i], self.mins[i], self.scales[i], max_level);
+        }
+        codes
+    }
+
+    pub fn dequantize(&self, codes: &[u8; D]) -> [f32; D] {
+        let mut vector = [0.0f32; D];
+        for i in 0..D {
+            vector[i] = self.mins[i] + codes[i] as f32 * self.scales[i];
+        }
+        vector
+    }
+
+    /// Bytes per packed code vector.
+    pub fn code_size(&self) -> usize {
+        if self.bits == 4 { D.div_ceil(2) } else { D }
+    }
+
+    /// Pack level indices, two 4-bit codes per byte (low nibble first) when bits is 4.
+    pub fn pack(&self, codes: &[u8; D]) -> Box<[u8]> {
+        if self.bits == 8 { Box::new(*codes) } else { pack_nibbles(codes) }
+    }
+
+    pub fn unpack(&self, packed: &[u8]) -> [u8; D] {
+        if self.bits == 8 { std::array::from_fn(|i| packed[i]) } else { unpack_nibbles(packed) }
+    }
+}
+
+/// Two 4-bit codes per byte, low nibble first.
+pub fn pack_nibbles<const D: usize>(codes: &[u8; D]) -> Box<[u8]> {
+    codes
+        .chunks(2)
+        .map(|pair| (pair[0] & 0x0f) | (pair.get(1).copied().unwrap_or(0) << 4))
+        .collect()
+}
+
+pub fn unpack_nibbles<const D: usize>(packed: &[u8]) -> [u8; D] {
+    std::array::from_fn(|i| (packed[i / 2] >> ((i % 2) * 4)) & 0x0f)
+}
+
+/// Distance between adjacent levels spanning [min, max], 0 when the range is empty or non-finite.
+pub fn level_scale(min: f32, max: f32, levels: u32) -> f32 {
+    let range = max - min;
+    if range.is_finite() && range > 0.0 && levels > 1 {
+        range / (levels - 1) as f32
+    } else {
+        0.0
+    }
+}
+
+/// Nearest level index for a value, saturating infinities and mapping NaN to level 0.
+pub fn quantize_level(value: f32, min: f32, scale: f32, max_level: u32) -> u8 {
+    // Base Case -> Degenerate range, every value shares the single level.
+    if scale == 0.0 || value.is_nan() {
+        return 0;
+    }
+    ((value - min) / scale).round().clamp(0.0, max_level as f32) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::vector_utils::scalar_quantize;
+
+    #[test]
+    fn calibrated_ranges_round_trip_and_handle_degenerate_inputs() {
+        let points = [[0.0, 5.0, -1.0, 2.0], [1.0, 5.0, 1.0, 4.0], [0.5, 5.0, 0.0, 3.0]];
+        let vectors: Vec<&[f32; 4]> = points.iter().collect();
+
+        let quantizer = ScalarQuantizer::calibrate(&vectors, 8, CalibrationScope::PerDimension).unwrap();
+        let decoded = quantizer.dequantize(&quantizer.quantize(&points[2]));
+        assert!((decoded[0] - 0.5).abs() <= 1.0 / 510.0 + f32::EPSILON);
+        assert_eq!(decoded[1], 5.0); // Constant dimension decodes exactly.
+        assert_eq!(quantizer.quantize(&[f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 9.0]), [0, 0, 0, 255]);
+        assert!(quantizer.covers(&points[0]) && !quantizer.covers(&[2.0, 5.0, 0.0, 3.0]));
+
+        // 4-bit codes pack two per byte and share one range across dimensions.
+        let nibbles = ScalarQuantizer::calibrate(&vectors, 4, CalibrationScope::Shared).unwrap();
+        let codes = nibbles.quantize(&[1.0, 5.0, 1.0, 3.0]);
+        assert_eq!(codes, [5, 15, 5, 10]);
+        assert_eq!(nibbles.code_size(), 2);
+        assert_eq!(nibbles.unpack(&nibbles.pack(&codes)), codes);
+
+        assert!(ScalarQuantizer::calibrate(&vectors, 6, CalibrationScope::Shared).is_err());
+        assert!(ScalarQuantizer::<4>::calibrate(&[], 8, CalibrationScope::Shared).is_err());
+
+        // Per-vector quantization no longer yields NaN codes or overflows u8.
+        assert_eq!(scalar_quantize::<3>(&[2.0, 2.0, 2.0], 256).unwrap(), [0, 0, 0]);
+        assert!(scalar_quantize::<3>(&[0.0, 1.0, 2.0], 1024).is_err());
+    }
+}
@@ -1,21 +1,142 @@
+use crate::error::TectonicError;
+use crate::utility::hashing_util::hash_vector_bits;
+use crate::utility::scalar_quantizer::{level_scale, quantize_level};
 
+pub fn scalar_quantize<const D: usize>(vec: &[f32], levels: u32) -> Result<[u8; D], TectonicError> {
+        // Base Case -> Vector length does not match array size D.
+        if vec.len() != D {
+            return Err(TectonicError::DimensionMismatch { expected: D, actual: vec.len() });
+        }
 
-pub fn scalar_quantize<const D: usize>(vec: &[f32], levels: u32) -> [u8; D] {
+        // Base Case -> Level indices must fit in a u8.
+        if !(2..=256).contains(&levels) {
+            return Err(TectonicError::InvalidConfig(format!("quantization levels must be within [2, 256], got {}", levels)));
+        }
+
+        let min = vec.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = vec.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+        // Constant vectors have a zero scale and collapse onto level 0.
+        let scale = level_scale(min, max, levels);
+
+        let mut quantized = [0u8; D];
+        for (code, x) in quantized.iter_mut().zip(vec) {
+            *code = quantize_level(*x, min, scale, levels - 1);
+        }
+        Ok(quantized)
+    }
+
+    /// Affine encoding of a vector onto 2^bits levels (bits <= 8) -> (codes, min, scale), where x ≈ min + code * scale.
+    pub fn scalar_encode<const D: usize>(vec: &[f32; D], bits: u32) -> ([u8; D], f32, f32) {
         let min = vec.iter().cloned().fold(f32::INFINITY, f32::min);
         let max = vec.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
 
-        let scale = (max - min) / (levels as f32 - 1.0);
+        // Constant (or empty) vectors collapse onto a single level.
+        let levels = 1 << bits;
+        let scale = level_scale(min, max, levels);
+        let min = if min.is_finite() { min } else { 0.0 };
+        (vec.map(|x| quantize_level(x, min, scale, levels - 1)), min, scale)
+    }
+
+    pub fn scalar_decode<const D: usize>(codes: &[u8; D], min: f32, scale: f32) -> [f32; D] {
+        codes.map(|code| min + code as f32 * scale)
+    }
+
+    /// Round an f32 to the nearest IEEE 754 binary16 bit pattern (ties to even).
+    pub fn f32_to_f16(value: f32) -> u16 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exponent = ((bits >> 23) & 0xff) as i32;
+        let mantissa = bits & 0x007f_ffff;
+
+        // Base Case -> Infinity stays infinite, NaN stays a (quiet) NaN.
+        if exponent == 0xff {
+            return sign | 0x7c00 | if mantissa != 0 { 0x0200 } else { 0 };
+        }
+
+        let rebiased = exponent - 127 + 15;
+        if rebiased >= 0x1f {
+            return sign | 0x7c00;
+        }
+
+        // Values below the normal range become subnormals (or signed zero).
+        let (half, shift) = if rebiased <= 0 {
+            if rebiased < -10 {
+                return sign;
+            }
+            let shift = (14 - rebiased) as u32;
+            ((mantissa | 0x0080_0000) >> shift, shift)
+        } else {
+            (((rebiased as u32) << 10) | (mantissa >> 13), 13)
+        };
+
+        let full = if rebiased <= 0 { mantissa | 0x0080_0000 } else { mantissa };
+        let remainder = full & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let rounded = if remainder > halfway || (remainder == halfway && half & 1 == 1) { half + 1 } else { half };
+
+        // A carry out of the mantissa correctly bumps the exponent (up to infinity).
+        sign | rounded as u16
+    }
+
+    pub fn f16_to_f32(bits: u16) -> f32 {
+        let sign = ((bits & 0x8000) as u32) << 16;
+        let exponent = ((bits >> 10) & 0x1f) as u32;
+        let mantissa = (bits & 0x03ff) as u32;
+
+        match exponent {
+            0 => {
+                let magnitude = mantissa as f32 * f32::powi(2.0, -24);
+                if sign != 0 { -magnitude } else { magnitude }
+            }
+            0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+            _ => f32::from_bits(sign | ((exponent + 112) << 23) | (mantissa << 13)),
+        }
+    }
+
+    /// Round an f32 to the nearest bfloat16 bit pattern (ties to even).
+    pub fn f32_to_bf16(value: f32) -> u16 {
+        let bits = value.to_bits();
+
+        // Base Case -> Keep NaNs quiet instead of rounding them into infinity.
+        if value.is_nan() {
+            return ((bits >> 16) as u16) | 0x0040;
+        }
+
+        let rounding = 0x7fff + ((bits >> 16) & 1);
+        ((bits + rounding) >> 16) as u16
+    }
 
-        let quantized: Vec<u8> = vec.iter()
-            .map(|&x| {
-                let q = ((x - min) / scale).round();
-                q.clamp(0.0, (levels - 1) as f32) as u8
-            })
-            .collect();
-    
-        quantized.try_into().expect("Vector length does not match array size D")
+    pub fn bf16_to_f32(bits: u16) -> f32 {
+        f32::from_bits((bits as u32) << 16)
+    }
+
+    /// Pack the sign bit of every component (set when positive) into u64 words.
+    pub fn binary_quantize<const D: usize>(vec: &[f32; D]) -> Box<[u64]> {
+        let mut words = vec![0u64; D.div_ceil(64)];
+        for (idx, value) in vec.iter().enumerate() {
+            if *value > 0.0 {
+                words[idx / 64] |= 1 << (idx % 64);
+            }
+        }
+        words.into_boxed_slice()
+    }
+
+    pub fn hamming_distance(x: &[u64], y: &[u64]) -> u32 {
+        x.iter().zip(y).map(|(a, b)| (a ^ b).count_ones()).sum()
+    }
+
+    pub fn generate_vector_key<const D: usize>(vec: &[f32; D]) -> u64 {
+        // Hash of the exact f32 bits shared by duplicate detection across partitions.
+        // Scaled or shifted copies quantize identically per vector, so they must not share a key.
+        hash_vector_bits(vec)
     }
 
     pub fn generate_vector_unique_id(x: u64, y: u64) -> u64 {
         (x << 32) | y
+    }
+
+    pub fn decode_vector_unique_id(id: u64) -> (u64, u64) {
+        // Inverse of generate_vector_unique_id -> (partition_id, entry counter).
+        (id >> 32, id & 0xFFFF_FFFF)
     }
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod vector_entry;
-pub mod vector_data;
\ No newline at end of file
+pub mod vector_data;
+pub mod stored_vector;
\ No newline at end of file
@@ -0,0 +1,302 @@
+use crate::search::distance_metric::DistanceMetricDyn;
+use crate::utility::product_quantizer::{AdcTable, ProductQuantizer, ProductQuantizerConfig};
+use crate::utility::scalar_quantizer::{pack_nibbles, unpack_nibbles, ScalarQuantizer};
+use crate::utility::vector_utils::{bf16_to_f32, f16_to_f32, f32_to_bf16, f32_to_f16, scalar_decode, scalar_encode};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Representation shards use for stored vectors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StorageMode {
+    /// Full-precision f32 components.
+    #[default]
+    Full,
+
+    /// 8-bit scalar codes (~4x smaller), per-vector min/scale until a rebuild calibrates a partition-wide range.
+    Int8,
+
+    /// 4-bit scalar codes packed two per byte (~8x smaller), with the same per-vector and calibrated ranges as Int8.
+    Int4,
+
+    /// 16-bit floats converted on insert (2x smaller), scored with f32 accumulation.
+    Half(HalfFormat),
+
+    /// Product-quantized codes from a codebook trained per partition on rebuild.
+    /// Vectors stay in full precision until their partition has a trained codec.
+    Product(ProductQuantizerConfig),
+}
+
+impl StorageMode {
+    /// Whether the mode loses enough precision that retained f32 copies are worth re-ranking against.
+    pub fn supports_rerank(&self) -> bool {
+        matches!(self, StorageMode::Int8 | StorageMode::Int4 | StorageMode::Product(_))
+    }
+
+    /// Bits per scalar code, for the modes that store them.
+    pub fn scalar_bits(&self) -> Option<u32> {
+        match self {
+            StorageMode::Int8 => Some(8),
+            StorageMode::Int4 => Some(4),
+            StorageMode::Full | StorageMode::Half(_) | StorageMode::Product(_) => None,
+        }
+    }
+}
+
+/// 16-bit floating point layout used by half-precision storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HalfFormat {
+    /// IEEE 754 binary16: 5 exponent bits, 10 mantissa bits.
+    F16,
+
+    /// bfloat16: the f32 exponent range with 7 mantissa bits.
+    BF16,
+}
+
+impl HalfFormat {
+    pub fn encode(&self, value: f32) -> u16 {
+        match self {
+            HalfFormat::F16 => f32_to_f16(value),
+            HalfFormat::BF16 => f32_to_bf16(value),
+        }
+    }
+
+    pub fn decode(&self, bits: u16) -> f32 {
+        match self {
+            HalfFormat::F16 => f16_to_f32(bits),
+            HalfFormat::BF16 => bf16_to_f32(bits),
+        }
+    }
+}
+
+/// Stored form of an entry's vector. Components live on the heap so that
+/// quantized entries actually shrink the shard storage.
+#[derive(Clone, Debug)]
+pub enum StoredVector<const D: usize> {
+    /// Full-precision components.
+    Full(Box<[f32; D]>),
+
+    /// Codes decoded as `min + code * scale`, optionally retaining the source vector for re-ranking.
+    Int8 {
+        codes: Box<[u8; D]>,
+        min: f32,
+        scale: f32,
+        original: Option<Box<[f32; D]>>,
+    },
+
+    /// Nibble-packed codes (see `pack_nibbles`) decoded like `Int8`.
+    Int4 {
+        codes: Box<[u8]>,
+        min: f32,
+        scale: f32,
+        original: Option<Box<[f32; D]>>,
+    },
+
+    /// Half-precision components in the given format.
+    Half {
+        bits: Box<[u16; D]>,
+        format: HalfFormat,
+    },
+
+    /// One codebook index per subspace of the codec that produced them.
+    Product {
+        codes: Box<[u8]>,
+        codec: Arc<ProductQuantizer<D>>,
+        original: Option<Box<[f32; D]>>,
+    },
+}
+
+impl<const D: usize> StoredVector<D> {
+    /// Encode in the given mode, product quantization requires a codec (see `product`).
+    pub fn encode(vector: &[f32; D], mode: StorageMode, retain_original: bool) -> Self {
+        match mode {
+            StorageMode::Full | StorageMode::Product(_) => Self::Full(Box::new(*vector)),
+            StorageMode::Half(format) => Self::Half { bits: Box::new(vector.map(|x| format.encode(x))), format },
+            StorageMode::Int8 => {
+                let (codes, min, scale) = scalar_encode(vector, 8);
+                Self::scalar(codes, 8, min, scale, retain_original.then(|| Box::new(*vector)))
+            }
+            StorageMode::Int4 => {
+                let (codes, min, scale) = scalar_encode(vector, 4);
+                Self::scalar(codes, 4, min, scale, retain_original.then(|| Box::new(*vector)))
+            }
+        }
+    }
+
+    /// Int8 or Int4 vector holding the given level indices.
+    fn scalar(codes: [u8; D], bits: u32, min: f32, scale: f32, original: Option<Box<[f32; D]>>) -> Self {
+        match bits {
+            8 => Self::Int8 { codes: Box::new(codes), min, scale, original },
+            _ => Self::Int4 { codes: pack_nibbles(&codes), min, scale, original },
+        }
+    }
+
+    pub fn product(vector: &[f32; D], codec: &Arc<ProductQuantizer<D>>, retain_original: bool) -> Self {
+        Self::Product {
+            codes: codec.encode(vector),
+            codec: Arc::clone(codec),
+            original: retain_original.then(|| Box::new(*vector)),
+        }
+    }
+
+    /// Int8 or Int4 codes (by the quantizer's bits) on a grid calibrated with `CalibrationScope::Shared`.
+    /// Components outside the grid saturate, so callers check `ScalarQuantizer::covers` first.
+    pub fn calibrated(vector: &[f32; D], quantizer: &ScalarQuantizer<D>, retain_original: bool) -> Self {
+        let (min, scale) = quantizer.shared_range();
+        Self::scalar(quantizer.quantize(vector), quantizer.bits(), min, scale, retain_original.then(|| Box::new(*vector)))
+    }
+
+    /// Whether the vector holds codes of the quantizer's width on its shared grid.
+    pub fn is_calibrated_by(&self, quantizer: &ScalarQuantizer<D>) -> bool {
+        match self {
+            Self::Int8 { min, scale, .. } => quantizer.bits() == 8 && (*min, *scale) == quantizer.shared_range(),
+            Self::Int4 { min, scale, .. } => quantizer.bits() == 4 && (*min, *scale) == quantizer.shared_range(),
+            _ => false,
+        }
+    }
+
+    /// Full-precision view, borrowed when available and decoded otherwise.
+    pub fn to_f32(&self) -> Cow<'_, [f32; D]> {
+        match self {
+            Self::Full(vector) => Cow::Borrowed(vector),
+            Self::Int8 { original: Some(original), .. }
+            | Self::Int4 { original: Some(original), .. }
+            | Self::Product { original: Some(original), .. } => Cow::Borrowed(original),
+            Self::Int8 { codes, min, scale, .. } => Cow::Owned(scalar_decode(codes, *min, *scale)),
+            Self::Int4 { codes, min, scale, .. } => Cow::Owned(scalar_decode(&unpack_nibbles(codes), *min, *scale)),
+            Self::Half { bits, format } => Cow::Owned(bits.map(|x| format.decode(x))),
+            Self::Product { codes, codec, .. } => Cow::Owned(codec.decode(codes)),
+        }
+    }
+
+    /// Source vector, when stored losslessly or retained for re-ranking.
+    pub fn original(&self) -> Option<&[f32; D]> {
+        match self {
+            Self::Full(vector) => Some(vector),
+            Self::Int8 { original, .. } | Self::Int4 { original, .. } | Self::Product { original, .. } => original.as_deref(),
+            Self::Half { .. } => None,
+        }
+    }
+
+    /// Distance from the query, scored directly on the stored representation.
+    pub fn distance(&self, query: &[f32; D], metric: &dyn DistanceMetricDyn<D>) -> f32 {
+        match self {
+            Self::Full(vector) => metric.distance(query, vector),
+            Self::Int8 { codes, min, scale, .. } => metric.distance_int8(query, codes, *min, *scale),
+            Self::Int4 { codes, min, scale, .. } => metric.distance_int8(query, &unpack_nibbles(codes), *min, *scale),
+            Self::Half { bits, format } => metric.distance_half(query, bits, *format),
+            Self::Product { codes, codec, .. } => metric.distance(query, &codec.decode(codes)),
+        }
+    }
+
+    /// Distance on the stored representation, answering product codes from the query's lookup table.
+    pub fn table_distance(&self, query: &[f32; D], metric: &dyn DistanceMetricDyn<D>, table: Option<&AdcTable<'_, D>>) -> f32 {
+        match (self, table) {
+            (Self::Product { codes, codec, .. }, Some(table)) if table.scores(codec) => table.distance(codes),
+            _ => self.distance(query, metric),
+        }
+    }
+
+    /// Whether the vector holds codes produced by the given codec.
+    pub fn is_encoded_by(&self, quantizer: &Arc<ProductQuantizer<D>>) -> bool {
+        matches!(self, Self::Product { codec, .. } if Arc::ptr_eq(codec, quantizer))
+    }
+
+    /// Distance against the retained full-precision vector, falling back to the stored form.
+    pub fn exact_distance(&self, query: &[f32; D], metric: &dyn DistanceMetricDyn<D>) -> f32 {
+        match self {
+            Self::Int8 { original: Some(original), .. }
+            | Self::Int4 { original: Some(original), .. }
+            | Self::Product { original: Some(original), .. } => {
+                metric.distance(query, original)
+            }
+            _ => self.distance(query, metric),
+        }
+    }
+
+    pub fn is_quantized(&self) -> bool {
+        !matches!(self, Self::Full(_))
+    }
+
+    /// Heap bytes held by the stored representation.
+    pub fn size_bytes(&self) -> usize {
+        match self {
+            Self::Full(_) => size_of::<[f32; D]>(),
+            Self::Int8 { original, .. } => {
+                size_of::<[u8; D]>() + 2 * size_of::<f32>() + original.as_ref().map_or(0, |_| size_of::<[f32; D]>())
+            }
+            Self::Int4 { codes, original, .. } => {
+                codes.len() + 2 * size_of::<f32>() + original.as_ref().map_or(0, |_| size_of::<[f32; D]>())
+            }
+            Self::Half { .. } => size_of::<[u16; D]>(),
+            Self::Product { codes, original, .. } => {
+                codes.len() + size_of::<Arc<ProductQuantizer<D>>>() + original.as_ref().map_or(0, |_| size_of::<[f32; D]>())
+            }
+        }
+    }
+}
+
+impl<const D: usize> From<[f32; D]> for StoredVector<D> {
+    fn from(vector: [f32; D]) -> Self {
+        Self::Full(Box::new(vector))
+    }
+}
+
+impl<const D: usize> PartialEq<[f32; D]> for StoredVector<D> {
+    fn eq(&self, other: &[f32; D]) -> bool {
+        *self.to_f32() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::euclidean_strategy::EuclideanProduct;
+
+    #[test]
+    fn int8_storage_scores_close_to_full_precision() {
+        let vector = [0.5, -1.25, 3.0, 0.0];
+        let full = StoredVector::encode(&vector, StorageMode::Full, false);
+        let quantized = StoredVector::encode(&vector, StorageMode::Int8, false);
+        let retained = StoredVector::encode(&vector, StorageMode::Int8, true);
+        let query = [1.0, 1.0, 1.0, 1.0];
+
+        let exact = full.distance(&query, &EuclideanProduct);
+        assert!((quantized.distance(&query, &EuclideanProduct) - exact).abs() < 0.05);
+        assert_eq!(retained.exact_distance(&query, &EuclideanProduct), exact);
+        assert!(quantized.size_bytes() < full.size_bytes());
+
+        // Constant vectors decode without dividing by a zero range.
+        let constant = StoredVector::encode(&[2.0; 4], StorageMode::Int8, false);
+        assert_eq!(constant, [2.0; 4]);
+
+        // 4-bit codes halve the int8 footprint at 16 levels per range.
+        let nibbles = StoredVector::encode(&vector, StorageMode::Int4, false);
+        assert_eq!(nibbles.size_bytes(), 2 + 8);
+        for (decoded, original) in nibbles.to_f32().iter().zip(vector) {
+            assert!((decoded - original).abs() <= 4.25 / 30.0 + f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn half_precision_round_trips_within_format_precision() {
+        assert_eq!(HalfFormat::F16.encode(1.0), 0x3c00);
+        assert_eq!(HalfFormat::F16.encode(65504.0), 0x7bff);
+        assert_eq!(HalfFormat::F16.encode(70000.0), 0x7c00);
+        assert_eq!(HalfFormat::F16.encode(6e-8), 0x0001);
+        assert_eq!(HalfFormat::F16.decode(HalfFormat::F16.encode(-2.5)), -2.5);
+        assert!(HalfFormat::F16.decode(HalfFormat::F16.encode(f32::NAN)).is_nan());
+        assert_eq!(HalfFormat::BF16.encode(1.0), 0x3f80);
+        // bfloat16 keeps the f32 exponent range that binary16 overflows.
+        assert_eq!(HalfFormat::F16.encode(1e30), 0x7c00);
+        assert!((HalfFormat::BF16.decode(HalfFormat::BF16.encode(1e30)) / 1e30 - 1.0).abs() < 0.004);
+
+        let vector = [0.1, -0.7, 3.3, 12.0];
+        for format in [HalfFormat::F16, HalfFormat::BF16] {
+            let stored = StoredVector::encode(&vector, StorageMode::Half(format), false);
+            assert_eq!(stored.size_bytes(), 8);
+            for (decoded, original) in stored.to_f32().iter().zip(vector) {
+                assert!((decoded - original).abs() <= original.abs() * 0.005);
+            }
+        }
+    }
+}
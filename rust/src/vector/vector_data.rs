@@ -1,30 +1,95 @@
+use crate::utility::time_util::timestamp_nanos;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
+#[derive(Debug)]
 #[repr(align(32))]
 pub struct VectorData {
     /// Unique identifier for the vector data (Immutable).
     pub data_id: u64,
 
     /// Number of times the vector data has been accessed (Mutable).
-    pub access_count: u64,
+    pub access_count: AtomicU64,
 
     /// Timestamp for last time vector entry was accessed (Mutable).
-    pub last_accessed: u64, 
+    pub last_accessed: AtomicU64,
+
+    /// Total latency in nanoseconds accumulated from all accesses (Mutable).
+    pub combined_latency: AtomicU64,
 
-    /// Total latency accumulated from all accesses (Mutable).
-    pub combined_latency: u64,
+    /// Average latency per access in nanoseconds, stored as f64 bits (Mutable).
+    pub average_latency: AtomicU64,
+}
 
-    /// Average latency per access (Mutable).
-    pub average_latency: f64,
+/// Point-in-time copy of an entry's access statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryStats {
+    pub entry_id: u64,
+    pub access_count: u64,
+    pub last_accessed: u64,
+    pub combined_latency: Duration,
+    pub average_latency: Duration,
 }
 
 impl VectorData {
     pub fn new(id: u64) -> Self {
         Self {
             data_id: id,
-            access_count: 0,
-            last_accessed: 0,
-            combined_latency: 0,
-            average_latency: 0.0,
+            access_count: AtomicU64::new(0),
+            last_accessed: AtomicU64::new(timestamp_nanos()),
+            combined_latency: AtomicU64::new(0),
+            average_latency: AtomicU64::new(0.0f64.to_bits()),
+        }
+    }
+
+    /// Record a query hit served within the given latency.
+    /// Updates are atomic so hits can be recorded through shared references.
+    pub fn record_access(&self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        let count = self.access_count.fetch_add(1, Ordering::Relaxed) + 1;
+        let combined = self.combined_latency.fetch_add(nanos, Ordering::Relaxed).saturating_add(nanos);
+
+        self.average_latency.store((combined as f64 / count as f64).to_bits(), Ordering::Relaxed);
+        self.touch();
+    }
+
+    /// Mark the entry as recently used without counting an access (e.g. on overwrite).
+    pub fn touch(&self) {
+        self.last_accessed.fetch_max(timestamp_nanos(), Ordering::Relaxed);
+    }
+
+    pub fn access_count(&self) -> u64 {
+        self.access_count.load(Ordering::Relaxed)
+    }
+
+    pub fn last_accessed(&self) -> u64 {
+        self.last_accessed.load(Ordering::Relaxed)
+    }
+
+    pub fn average_latency(&self) -> f64 {
+        f64::from_bits(self.average_latency.load(Ordering::Relaxed))
+    }
+
+    pub fn stats(&self) -> EntryStats {
+        EntryStats {
+            entry_id: self.data_id,
+            access_count: self.access_count(),
+            last_accessed: self.last_accessed(),
+            combined_latency: Duration::from_nanos(self.combined_latency.load(Ordering::Relaxed)),
+            average_latency: Duration::from_nanos(self.average_latency() as u64),
         }
     }
-}
\ No newline at end of file
+}
+
+impl Clone for VectorData {
+    fn clone(&self) -> Self {
+        let copy = |value: &AtomicU64| AtomicU64::new(value.load(Ordering::Relaxed));
+        Self {
+            data_id: self.data_id,
+            access_count: copy(&self.access_count),
+            last_accessed: copy(&self.last_accessed),
+            combined_latency: copy(&self.combined_latency),
+            average_latency: copy(&self.average_latency),
+        }
+    }
+}
@@ -1,25 +1,58 @@
 use crate::utility::hashing_util;
+use crate::utility::vector_utils::generate_vector_key;
+use crate::vector::stored_vector::StoredVector;
+use crate::vector::vector_data::VectorData;
 
 #[derive(Clone)]
 #[repr(align(32))]
-pub struct VectorEntry<const D: usize> {
+pub struct VectorEntry<const D: usize, V = ()> {
     /// Unique identifier for the vector entry (Immutable).
     pub entry_id: u64,
 
-    /// High-dimensional vector data (Immutable).
-    pub vector: [f32; D],
+    /// High-dimensional vector data in the partition's storage mode (Immutable).
+    pub vector: StoredVector<D>,
+
+    /// Key of the vector as inserted, indexed by the partition key map (Immutable).
+    pub vector_key: u64,
+
+    /// Packed sign bits for Hamming prefiltering, present when binary queries are enabled (Immutable).
+    pub signature: Option<Box<[u64]>>,
 
     /// Unique hash-value for entry key (Immutable).
     pub key_hash: u64,
+
+    /// Access statistics used by eviction policies (Mutable).
+    pub data: VectorData,
+
+    /// Cached payload associated with the vector (Mutable).
+    pub value: V,
 }
 
-impl <const D: usize> VectorEntry<D> {
-    pub fn new(id: u64, vector: [f32; D]) -> Self {
+impl <const D: usize, V> VectorEntry<D, V> {
+    pub fn new(id: u64, vector: [f32; D], value: V) -> Self {
+        let vector_key = generate_vector_key(&vector);
+        Self::stored(id, StoredVector::from(vector), vector_key, value)
+    }
+
+    /// Whether the entry was stored from exactly this vector, compared bit-for-bit.
+    /// Lossy entries without a retained original can only be matched by their key.
+    pub fn matches(&self, vector: &[f32; D], vector_key: u64) -> bool {
+        self.vector_key == vector_key
+            && self.vector.original().is_none_or(|original| original.iter().zip(vector).all(|(a, b)| a.to_bits() == b.to_bits()))
+    }
+
+    /// Build an entry around an already encoded vector.
+    /// The key is taken from the source vector, so lossy storage never re-keys an entry.
+    pub fn stored(id: u64, vector: StoredVector<D>, vector_key: u64, value: V) -> Self {
         let hash_key = hashing_util::hash_u64(id);
         Self {
             entry_id: id,
             vector,
+            vector_key,
+            signature: None,
             key_hash: hash_key,
+            data: VectorData::new(id),
+            value,
         }
     }
 }
\ No newline at end of file